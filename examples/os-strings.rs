@@ -1,5 +1,5 @@
 fn main() {
-    println!("WhoAmI {}", env!("CARGO_PKG_VERSION"));
+    println!("WhoAmI {}", whoami::crate_version());
     println!();
     println!(
         "User's Language        whoami::langs():                 {:?}",
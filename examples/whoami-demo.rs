@@ -1,5 +1,5 @@
 fn main() {
-    println!("WhoAmI {}", env!("CARGO_PKG_VERSION"));
+    println!("WhoAmI {}", whoami::crate_version());
     println!();
     println!(
         "User's Language        whoami::langs():               {}",
@@ -46,4 +46,8 @@ fn main() {
         "Device's CPU Arch      whoami::arch():                {}",
         whoami::arch(),
     );
+    println!(
+        "Device's Endianness    whoami::endianness():          {}",
+        whoami::endianness(),
+    );
 }
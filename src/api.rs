@@ -1,9 +1,14 @@
-use std::ffi::OsString;
+use std::{
+    env,
+    ffi::OsString,
+    io::{Error, ErrorKind},
+};
 
 use crate::{
     fallible,
-    os::{Os, Target},
-    Arch, DesktopEnv, Language, Platform, Result,
+    os::{self, Backend, Os, Target},
+    Arch, ColorSupport, DesktopEnv, Endianness, Language, LibcKind, Platform,
+    Result,
 };
 
 macro_rules! report_message {
@@ -15,21 +20,235 @@ macro_rules! report_message {
 const DEFAULT_USERNAME: &str = "Unknown";
 const DEFAULT_HOSTNAME: &str = "LocalHost";
 
+/// Environment variables set by CI services, and the name of each service
+///
+/// Checked in order; the generic `CI` variable is checked last.
+const CI_VARS: &[(&str, &str)] = &[
+    ("GITHUB_ACTIONS", "GitHub Actions"),
+    ("GITLAB_CI", "GitLab CI"),
+    ("BUILDKITE", "Buildkite"),
+    ("JENKINS_URL", "Jenkins"),
+    ("TF_BUILD", "Azure Pipelines"),
+    ("CIRCLECI", "CircleCI"),
+    ("TRAVIS", "Travis CI"),
+    ("APPVEYOR", "AppVeyor"),
+    ("TEAMCITY_VERSION", "TeamCity"),
+    ("BITBUCKET_BUILD_NUMBER", "Bitbucket Pipelines"),
+    ("CODEBUILD_BUILD_ID", "AWS CodeBuild"),
+    ("CIRRUS_CI", "Cirrus CI"),
+    ("DRONE", "Drone"),
+    ("SEMAPHORE", "Semaphore"),
+    ("WOODPECKER", "Woodpecker"),
+];
+
+/// Get the version of the whoami crate that is linked.
+///
+/// Useful for including in bug reports and diagnostic output, since results
+/// may differ between versions.
+///
+/// Example: "1.5.1"
+#[inline(always)]
+pub const fn crate_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
 /// Get the CPU Architecture.
+///
+/// Panics if the architecture can't be detected.  Use [`try_arch()`] to handle
+/// the error instead.
 #[inline(always)]
 pub fn arch() -> Arch {
     Target::arch(Os).expect(concat!("arch() failed.  ", report_message!()))
 }
 
+/// Get the CPU Architecture, returning an error instead of panicking if it
+/// can't be detected.
+///
+/// Same as [`fallible::arch()`].
+#[inline(always)]
+pub fn try_arch() -> Result<Arch> {
+    fallible::arch()
+}
+
+/// Returns true if running inside a Flatpak or Snap sandbox on Linux.
+///
+/// Inside a sandbox, some information (such as the processes outside of it)
+/// isn't available, so [`desktop_env()`] relies on the session environment
+/// variables passed through to the sandbox.
+#[inline(always)]
+pub fn is_sandboxed() -> bool {
+    Backend::sandboxed(Os)
+}
+
+/// Returns true if standard output is a terminal (rather than redirected to a
+/// file or pipe).
+#[inline(always)]
+pub fn is_tty() -> bool {
+    Backend::tty(Os)
+}
+
+/// Get the colors supported by the terminal on standard output.
+///
+/// Returns [`ColorSupport::None`] if standard output isn't a terminal, or if
+/// the `NO_COLOR` environment variable is set.  Otherwise, this is detected
+/// from the `COLORTERM` and `TERM` environment variables, and on Windows, from
+/// whether the console has virtual terminal processing enabled.
+#[inline(always)]
+pub fn color_support() -> ColorSupport {
+    Backend::color_support(Os)
+}
+
+/// Returns true if running in a continuous integration environment.
+///
+/// See [`ci_name()`] for how this is detected.
+#[inline(always)]
+pub fn is_ci() -> bool {
+    ci_name().is_some()
+}
+
+/// Get the name of the continuous integration service this is running in, or
+/// `None` if not running in CI.
+///
+/// Detected from the environment variables set by common CI services (such as
+/// `GITHUB_ACTIONS` or `GITLAB_CI`).  Other services are detected from the
+/// generic `CI` variable (unless it's set to "false" or "0"), and named
+/// "Unknown".
+///
+/// Example: "GitHub Actions"
+pub fn ci_name() -> Option<&'static str> {
+    ci_name_from(|name| env::var_os(name))
+}
+
+/// Get the name of the CI service from the environment variables, as returned
+/// by `var`.
+fn ci_name_from(
+    var: impl Fn(&str) -> Option<OsString>,
+) -> Option<&'static str> {
+    if let Some(&(_, name)) =
+        CI_VARS.iter().find(|(name, _)| var(name).is_some())
+    {
+        return Some(name);
+    }
+
+    match var("CI") {
+        Some(ref ci) if ci != "false" && ci != "0" && !ci.is_empty() => {
+            Some("Unknown")
+        }
+        _ => None,
+    }
+}
+
+/// Get the byte order of the target this crate was compiled for.
+#[inline(always)]
+pub fn endianness() -> Endianness {
+    if cfg!(target_endian = "big") {
+        Endianness::Big
+    } else {
+        Endianness::Little
+    }
+}
+
+/// Get a best-effort Rust target triple for the host, such as
+/// "x86_64-unknown-linux-gnu", for selecting prebuilt binaries.
+///
+/// The CPU architecture and (on Linux) the C library are detected at runtime,
+/// while the rest is based on the target this crate was compiled for.  The
+/// returned triple is not guaranteed to be one that Rust supports.
+pub fn target_triple() -> String {
+    let arch = fallible::arch().unwrap_or_else(|_| Arch::current());
+    let cpu = match &arch {
+        Arch::ArmV5 => "armv5te",
+        Arch::ArmV6 => "arm",
+        Arch::ArmV7 => "armv7",
+        Arch::Arm64 => "aarch64",
+        Arch::I386 | Arch::I586 => "i586",
+        Arch::Riscv32 => "riscv32gc",
+        Arch::Riscv64 => "riscv64gc",
+        Arch::X32 => "x86_64",
+        other => other.name(),
+    };
+    // ABI suffix of the environment, for Linux and Android
+    let abi = match arch {
+        Arch::ArmV5 => "eabi",
+        Arch::ArmV6 | Arch::ArmV7 => "eabihf",
+        Arch::Mips64 | Arch::Mips64El => "abi64",
+        Arch::X32 => "x32",
+        _ => "",
+    };
+    let os = match Platform::current() {
+        Platform::Linux => {
+            let libc = match fallible::libc_kind() {
+                Ok(LibcKind::Musl) => "musl",
+                _ => "gnu",
+            };
+
+            return format!("{}-unknown-linux-{}{}", cpu, libc, abi);
+        }
+        Platform::Android => {
+            let abi = if abi.starts_with("eabi") { "eabi" } else { "" };
+
+            return format!("{}-linux-android{}", cpu, abi);
+        }
+        Platform::Wasi => return format!("{}-wasip1", cpu),
+        Platform::Windows if cfg!(target_env = "gnu") => "pc-windows-gnu",
+        Platform::Windows => "pc-windows-msvc",
+        Platform::MacOS => "apple-darwin",
+        Platform::Ios => "apple-ios",
+        Platform::Bsd if cfg!(target_os = "dragonfly") => "unknown-dragonfly",
+        Platform::Bsd if cfg!(target_os = "netbsd") => "unknown-netbsd",
+        Platform::Bsd if cfg!(target_os = "openbsd") => "unknown-openbsd",
+        Platform::Bsd => "unknown-freebsd",
+        Platform::Illumos => "unknown-illumos",
+        Platform::Fuchsia => "unknown-fuchsia",
+        Platform::Redox => "unknown-redox",
+        Platform::Haiku => "unknown-haiku",
+        Platform::Nintendo => return "armv6k-nintendo-3ds".to_string(),
+        Platform::NintendoSwitch => "nintendo-switch-freestanding",
+        _ => "unknown-unknown",
+    };
+
+    format!("{}-{}", cpu, os)
+}
+
+/// Get the x86-64 microarchitecture level supported by the CPU, as defined by
+/// the x86-64 psABI.
+///
+/// Returns a level from 1 (the x86-64 baseline) to 4 (AVX-512), or `None` if
+/// not running on an x86 CPU that supports x86-64.  Levels 3 and 4 also require
+/// the operating system to support the AVX and AVX-512 registers.
+///
+/// Example: `Some(3)` on a CPU with AVX2
+#[inline(always)]
+pub fn x86_feature_level() -> Option<u8> {
+    os::x86_feature_level()
+}
+
 /// Get the user's username.
 ///
 /// On unix-systems this differs from [`realname()`] most notably in that spaces
 /// are not allowed in the username.
+///
+/// Returns "unknown" if the username can't be retrieved, or isn't valid UTF-8
+/// (which is possible on unix-systems).  Use [`username_lossy()`] or
+/// [`username_os()`] if the username may not be valid UTF-8.
 #[inline(always)]
 pub fn username() -> String {
     fallible::username().unwrap_or_else(|_| DEFAULT_USERNAME.to_lowercase())
 }
 
+/// Get the user's username, replacing any invalid UTF-8 sequences with
+/// [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+///
+/// Returns "unknown" if the username can't be retrieved.
+///
+/// [U+FFFD]: std::char::REPLACEMENT_CHARACTER
+#[inline(always)]
+pub fn username_lossy() -> String {
+    fallible::username_os()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| DEFAULT_USERNAME.to_lowercase())
+}
+
 /// Get the user's username.
 ///
 /// On unix-systems this differs from [`realname_os()`] most notably in that
@@ -40,19 +259,71 @@ pub fn username_os() -> OsString {
         .unwrap_or_else(|_| DEFAULT_USERNAME.to_lowercase().into())
 }
 
+/// Generate a "fancy" name from a username, by dropping any account server
+/// hostname, splitting words on `.`, `-`, `_` and camel case boundaries, and
+/// capitalizing each word.
+///
+/// Example: `jane.doe@example.com` or `JaneDoe` becomes `Jane Doe`
+fn fancy_fallback(username: &str) -> String {
+    let name = username.split('@').next().unwrap_or_default();
+    let mut fancy = String::with_capacity(name.len());
+    let mut prev: Option<char> = None;
+
+    for c in name.chars() {
+        if ['.', '-', '_'].contains(&c) {
+            prev = None;
+            continue;
+        }
+
+        let new_word = match prev {
+            Some(prev) => prev.is_lowercase() && c.is_uppercase(),
+            None => true,
+        };
+
+        if new_word {
+            if !fancy.is_empty() {
+                fancy.push(' ');
+            }
+
+            fancy.extend(c.to_uppercase());
+        } else {
+            fancy.push(c);
+        }
+
+        prev = Some(c);
+    }
+
+    if fancy.is_empty() {
+        return username.to_string();
+    }
+
+    fancy
+}
+
 /// Get the user's real (full) name.
+///
+/// If the user's real name isn't available, or isn't valid UTF-8, one is
+/// generated from the username (example: `jane.doe` becomes `Jane Doe`).
 #[inline(always)]
 pub fn realname() -> String {
     fallible::realname()
-        .or_else(|_| fallible::username())
+        .or_else(|_| fallible::username().map(|name| fancy_fallback(&name)))
         .unwrap_or_else(|_| DEFAULT_USERNAME.to_owned())
 }
 
 /// Get the user's real (full) name.
+///
+/// If the user's real name isn't available, one is generated from the
+/// username (example: `jane.doe` becomes `Jane Doe`).
 #[inline(always)]
 pub fn realname_os() -> OsString {
     fallible::realname_os()
-        .or_else(|_| fallible::username_os())
+        .or_else(|_| {
+            fallible::username_os().map(|name| match name.to_str() {
+                Some(name) => fancy_fallback(name).into(),
+                None => name,
+            })
+        })
         .unwrap_or_else(|_| DEFAULT_USERNAME.to_owned().into())
 }
 
@@ -130,16 +401,38 @@ pub fn distro_os() -> OsString {
 
 /// Get the desktop environment.
 ///
+/// Returns `DesktopEnv::Unknown` if it can't be detected.  Use
+/// [`try_desktop_env()`] to get an error instead.
+///
 /// Example: "gnome" or "windows"
 #[inline(always)]
 pub fn desktop_env() -> DesktopEnv {
-    Target::desktop_env(Os)
+    fallible::desktop_env().unwrap_or_default()
+}
+
+/// Get the desktop environment, returning an error if it can't be detected.
+///
+/// Same as [`fallible::desktop_env()`].
+#[inline(always)]
+pub fn try_desktop_env() -> Result<DesktopEnv> {
+    fallible::desktop_env()
 }
 
 /// Get the platform.
+///
+/// Returns `Platform::Unknown` if it can't be detected.  Use
+/// [`try_platform()`] to get an error instead.
 #[inline(always)]
 pub fn platform() -> Platform {
-    Target::platform(Os)
+    fallible::platform().unwrap_or_default()
+}
+
+/// Get the platform, returning an error if it can't be detected.
+///
+/// Same as [`fallible::platform()`].
+#[inline(always)]
+pub fn try_platform() -> Result<Platform> {
+    fallible::platform()
 }
 
 /// Get the user's preferred language(s).
@@ -166,26 +459,147 @@ pub fn lang() -> impl Iterator<Item = String> {
 /// Returned as iterator of [`Language`]s.  The most preferred language is
 /// returned first, followed by next preferred, and so on.  Unrecognized
 /// languages may either return an error or be skipped.
+///
+/// If no language is configured (such as in minimal containers, where the
+/// locale environment variables are unset or only set to the "C" locale), this
+/// falls back to `en/US`, the same as the fake backend.
 #[inline(always)]
 pub fn langs() -> Result<impl Iterator<Item = Language>> {
-    // FIXME: Could do less allocation
-    let langs = Target::langs(Os)?;
-    let langs = langs
-        .split(';')
-        .map(ToString::to_string)
-        .collect::<Vec<_>>();
-
-    Ok(langs.into_iter().filter_map(|lang| {
-        let lang = lang
-            .split_terminator('.')
-            .next()
-            .unwrap_or_default()
-            .replace(|x| ['_', '-'].contains(&x), "/");
-
-        if lang == "C" {
-            return None;
+    let mut langs = match Target::langs(Os) {
+        Ok(langs) => langs,
+        Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e),
+    };
+
+    if langs.is_empty() {
+        langs = os::parse_langs(&os::fake_langs());
+    }
+
+    Ok(langs.into_iter())
+}
+
+/// Get the system's preferred UI language(s), rather than the user's.
+///
+/// The most preferred language is returned first.  Only supported on Windows,
+/// where these are the languages installed system-wide.
+#[inline(always)]
+pub fn system_langs() -> Result<Vec<Language>> {
+    Backend::system_langs(Os)
+}
+
+/// Get the user's preferred language(s) as provided by the system, paired with
+/// the parsed [`Language`], or `None` if the language tag or locale is skipped
+/// by [`langs()`] (such as the "C" locale).
+///
+/// Unlike [`langs()`], duplicates are kept.  This is useful for diagnosing
+/// why a language is missing from [`langs()`].
+///
+/// Example: `[("en_US.UTF-8", Some(en/US)), ("C", None)]`
+#[inline(always)]
+pub fn langs_raw() -> Result<Vec<(String, Option<Language>)>> {
+    Ok(Backend::langs_raw(Os)?
+        .into_iter()
+        .map(|tag| {
+            let lang = Language::parse(&tag);
+
+            (tag, lang)
+        })
+        .collect())
+}
+
+/// Get the user's most preferred language.
+///
+/// Same as the first [`Language`] returned by [`langs()`], but returns an error
+/// if there are no preferred languages.
+#[inline(always)]
+pub fn primary_lang() -> Result<Language> {
+    langs()?.next().ok_or_else(|| {
+        Error::new(ErrorKind::NotFound, "No preferred languages")
+    })
+}
+
+/// Get the user's preferred primary language subtags, without regions or
+/// scripts, such as for a language picker.
+///
+/// Each language is kept at the position of its most preferred dialect.
+///
+/// Example: `["en", "fr"]` for `en-US`, `fr-FR` and `en-GB`
+pub fn primary_langs() -> Result<Vec<String>> {
+    let mut primary_langs: Vec<String> = Vec::new();
+
+    for lang in langs()? {
+        let lang = lang.to_string();
+        let primary = lang.split('/').next().unwrap_or_default();
+
+        if !primary_langs
+            .iter()
+            .any(|l| l.eq_ignore_ascii_case(primary))
+        {
+            primary_langs.push(primary.to_ascii_lowercase());
         }
+    }
+
+    Ok(primary_langs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars: Vec<(String, OsString)> = vars
+            .iter()
+            .map(|&(name, value)| (name.to_string(), value.into()))
+            .collect();
+
+        move |name| {
+            vars.iter()
+                .find(|(var, _)| var == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn ci_name_specific() {
+        for &(var, name) in CI_VARS {
+            assert_eq!(ci_name_from(vars(&[(var, "true")])), Some(name));
+        }
+    }
+
+    #[test]
+    fn ci_name_generic() {
+        assert_eq!(ci_name_from(vars(&[])), None);
+        assert_eq!(ci_name_from(vars(&[("CI", "true")])), Some("Unknown"));
+        assert_eq!(ci_name_from(vars(&[("CI", "1")])), Some("Unknown"));
+        assert_eq!(ci_name_from(vars(&[("CI", "false")])), None);
+        assert_eq!(ci_name_from(vars(&[("CI", "0")])), None);
+        assert_eq!(ci_name_from(vars(&[("CI", "")])), None);
+    }
+
+    #[test]
+    fn ci_name_specific_before_generic() {
+        assert_eq!(
+            ci_name_from(vars(&[("CI", "true"), ("GITLAB_CI", "true")])),
+            Some("GitLab CI"),
+        );
+        assert_eq!(
+            ci_name_from(vars(&[("CI", "false"), ("TRAVIS", "true")])),
+            Some("Travis CI"),
+        );
+    }
+
+    #[test]
+    fn fancy_fallback_words() {
+        assert_eq!(fancy_fallback("jane.doe@corp"), "Jane Doe");
+        assert_eq!(fancy_fallback("JaneDoe"), "Jane Doe");
+        assert_eq!(fancy_fallback("jane_doe"), "Jane Doe");
+        assert_eq!(fancy_fallback("jane-doe"), "Jane Doe");
+        assert_eq!(fancy_fallback("jane"), "Jane");
+    }
 
-        Some(Language::__(Box::new(lang)))
-    }))
+    #[test]
+    fn fancy_fallback_unchanged() {
+        assert_eq!(fancy_fallback("@corp"), "@corp");
+        assert_eq!(fancy_fallback("..."), "...");
+    }
 }
@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     fmt::{self, Display, Formatter},
     io::{Error, ErrorKind},
 };
@@ -6,27 +7,55 @@ use std::{
 use crate::Result;
 
 /// The address width of a CPU architecture
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
 #[non_exhaustive]
 pub enum Width {
     /// 32 bits
-    Bits32,
+    Bits32 = 32,
     /// 64 bits
-    Bits64,
+    Bits64 = 64,
 }
 
 impl Display for Width {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_str(match self {
-            Width::Bits32 => "32 bits",
-            Width::Bits64 => "64 bits",
+            Width::Bits32 => "32-bit",
+            Width::Bits64 => "64-bit",
+        })
+    }
+}
+
+impl Width {
+    /// Get the number of bits of this address width.
+    pub const fn bits(&self) -> u32 {
+        // The discriminant is the number of bits (`match` isn't allowed in a
+        // `const fn` before Rust 1.46)
+        *self as u32
+    }
+}
+
+/// The byte order of a CPU architecture
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[non_exhaustive]
+pub enum Endianness {
+    /// Least significant byte first
+    Little,
+    /// Most significant byte first
+    Big,
+}
+
+impl Display for Endianness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Endianness::Little => "little-endian",
+            Endianness::Big => "big-endian",
         })
     }
 }
 
 /// The architecture of a CPU
 #[non_exhaustive]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Arch {
     /// ARMv5
     ArmV5,
@@ -44,6 +73,8 @@ pub enum Arch {
     I686,
     /// X86_64 / Amd64
     X64,
+    /// x32 (X86_64 with 32-bit pointers)
+    X32,
     /// MIPS
     Mips,
     /// MIPS (LE)
@@ -76,13 +107,243 @@ pub enum Arch {
     Unknown(String),
 }
 
+/// The kind of an [`Arch`], without the payload of its `Unknown` variant.
+///
+/// Each variant corresponds to the [`Arch`] variant of the same name.  Unlike
+/// [`Arch`], this is [`Copy`].
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[non_exhaustive]
+pub enum ArchKind {
+    ArmV5,
+    ArmV6,
+    ArmV7,
+    Arm64,
+    I386,
+    I586,
+    I686,
+    X64,
+    X32,
+    Mips,
+    MipsEl,
+    Mips64,
+    Mips64El,
+    PowerPc,
+    PowerPc64,
+    PowerPc64Le,
+    Riscv32,
+    Riscv64,
+    S390x,
+    Sparc,
+    Sparc64,
+    Wasm32,
+    Wasm64,
+    Unknown,
+}
+
 impl Display for Arch {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         if let Self::Unknown(_) = self {
             f.write_str("Unknown: ")?;
         }
 
-        f.write_str(match self {
+        f.write_str(self.name())
+    }
+}
+
+impl Arch {
+    /// Get the CPU architecture this crate was compiled for, without querying
+    /// the operating system.
+    ///
+    /// This may differ from [`arch()`](crate::arch), for example when running
+    /// a 32-bit program on a 64-bit CPU.
+    #[inline(always)]
+    pub fn current() -> Self {
+        if cfg!(target_arch = "aarch64") {
+            Self::Arm64
+        } else if cfg!(all(target_arch = "arm", target_feature = "v7")) {
+            Self::ArmV7
+        } else if cfg!(all(target_arch = "arm", target_feature = "v6")) {
+            Self::ArmV6
+        } else if cfg!(target_arch = "arm") {
+            Self::ArmV5
+        } else if cfg!(all(target_arch = "x86", target_feature = "sse2")) {
+            Self::I686
+        } else if cfg!(target_arch = "x86") {
+            Self::I586
+        } else if cfg!(
+            all(target_arch = "x86_64", target_pointer_width = "32",)
+        ) {
+            Self::X32
+        } else if cfg!(target_arch = "x86_64") {
+            Self::X64
+        } else if cfg!(all(target_arch = "mips", target_endian = "little")) {
+            Self::MipsEl
+        } else if cfg!(target_arch = "mips") {
+            Self::Mips
+        } else if cfg!(all(target_arch = "mips64", target_endian = "little")) {
+            Self::Mips64El
+        } else if cfg!(target_arch = "mips64") {
+            Self::Mips64
+        } else if cfg!(target_arch = "powerpc") {
+            Self::PowerPc
+        } else if cfg!(
+            all(target_arch = "powerpc64", target_endian = "little",)
+        ) {
+            Self::PowerPc64Le
+        } else if cfg!(target_arch = "powerpc64") {
+            Self::PowerPc64
+        } else if cfg!(target_arch = "riscv32") {
+            Self::Riscv32
+        } else if cfg!(target_arch = "riscv64") {
+            Self::Riscv64
+        } else if cfg!(target_arch = "s390x") {
+            Self::S390x
+        } else if cfg!(target_arch = "sparc") {
+            Self::Sparc
+        } else if cfg!(target_arch = "sparc64") {
+            Self::Sparc64
+        } else if cfg!(target_arch = "wasm32") {
+            Self::Wasm32
+        } else if cfg!(target_arch = "wasm64") {
+            Self::Wasm64
+        } else {
+            Self::Unknown("Unknown".to_string())
+        }
+    }
+
+    /// Parse the CPU architecture of a Rust target triple, independent of the
+    /// target this crate was compiled for.
+    ///
+    /// Example: `Arch::X64` for "x86_64-unknown-linux-gnu"
+    pub fn from_target_str(target: &str) -> Self {
+        let cpu = target.split('-').next().unwrap_or_default();
+
+        match cpu {
+            "aarch64" | "aarch64_be" | "arm64" | "arm64e" | "arm64_32" => {
+                Self::Arm64
+            }
+            cpu if cpu.starts_with("armv7") || cpu.starts_with("thumbv7") => {
+                Self::ArmV7
+            }
+            cpu if cpu.starts_with("armv6") || cpu.starts_with("thumbv6") => {
+                Self::ArmV6
+            }
+            cpu if cpu.starts_with("armv5") || cpu.starts_with("armv4") => {
+                Self::ArmV5
+            }
+            "arm" | "armeb" => Self::ArmV6,
+            "i386" => Self::I386,
+            "i586" => Self::I586,
+            "i686" => Self::I686,
+            "x86_64" if target.ends_with("x32") => Self::X32,
+            "x86_64" | "x86_64h" => Self::X64,
+            "mips" | "mipsisa32r6" => Self::Mips,
+            "mipsel" | "mipsisa32r6el" => Self::MipsEl,
+            "mips64" | "mipsisa64r6" => Self::Mips64,
+            "mips64el" | "mipsisa64r6el" => Self::Mips64El,
+            "powerpc" | "powerpcle" => Self::PowerPc,
+            "powerpc64" => Self::PowerPc64,
+            "powerpc64le" => Self::PowerPc64Le,
+            cpu if cpu.starts_with("riscv32") => Self::Riscv32,
+            cpu if cpu.starts_with("riscv64") => Self::Riscv64,
+            "s390x" => Self::S390x,
+            "sparc" => Self::Sparc,
+            "sparc64" | "sparcv9" => Self::Sparc64,
+            "wasm32" => Self::Wasm32,
+            "wasm64" => Self::Wasm64,
+            cpu => Self::Unknown(cpu.to_string()),
+        }
+    }
+
+    /// Parse the machine hardware name reported by `uname`.
+    ///
+    /// Linux reports the same name for both byte orders of MIPS, so those are
+    /// cross-checked against the target this crate was compiled for.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "illumos",
+        target_os = "redox",
+    ))]
+    pub(crate) fn from_machine(machine: &str) -> Self {
+        let little_endian = cfg!(target_endian = "little");
+
+        match machine {
+            "aarch64" | "arm64" | "aarch64_be" | "armv8b" | "armv8l" => {
+                Self::Arm64
+            }
+            // Example: "armv5tejl", "armv6l" or "armv7l"
+            arch if arch.starts_with("armv5") => Self::ArmV5,
+            arch if arch.starts_with("armv6") => Self::ArmV6,
+            arch if arch.starts_with("armv7") => Self::ArmV7,
+            "arm" => Self::ArmV6,
+            "i386" => Self::I386,
+            "i586" => Self::I586,
+            "i686" | "i686-AT386" => Self::I686,
+            "mips" if little_endian => Self::MipsEl,
+            "mips" | "mipseb" => Self::Mips,
+            "mipsel" => Self::MipsEl,
+            "mips64" if little_endian => Self::Mips64El,
+            "mips64" | "mips64eb" => Self::Mips64,
+            "mips64el" => Self::Mips64El,
+            "powerpc" | "ppc" | "ppcle" | "macppc" => Self::PowerPc,
+            "powerpc64" | "ppc64" => Self::PowerPc64,
+            "powerpc64le" | "ppc64le" => Self::PowerPc64Le,
+            "riscv32" => Self::Riscv32,
+            "riscv64" | "riscv64gc" => Self::Riscv64,
+            "s390x" => Self::S390x,
+            "sparc" => Self::Sparc,
+            "sparc64" => Self::Sparc64,
+            "x86_64" | "amd64" => Self::X64,
+            _ => Self::Unknown(machine.to_string()),
+        }
+    }
+
+    /// Get the [`ArchKind`] of this CPU architecture.
+    pub fn kind(&self) -> ArchKind {
+        match self {
+            Self::ArmV5 => ArchKind::ArmV5,
+            Self::ArmV6 => ArchKind::ArmV6,
+            Self::ArmV7 => ArchKind::ArmV7,
+            Self::Arm64 => ArchKind::Arm64,
+            Self::I386 => ArchKind::I386,
+            Self::I586 => ArchKind::I586,
+            Self::I686 => ArchKind::I686,
+            Self::X64 => ArchKind::X64,
+            Self::X32 => ArchKind::X32,
+            Self::Mips => ArchKind::Mips,
+            Self::MipsEl => ArchKind::MipsEl,
+            Self::Mips64 => ArchKind::Mips64,
+            Self::Mips64El => ArchKind::Mips64El,
+            Self::PowerPc => ArchKind::PowerPc,
+            Self::PowerPc64 => ArchKind::PowerPc64,
+            Self::PowerPc64Le => ArchKind::PowerPc64Le,
+            Self::Riscv32 => ArchKind::Riscv32,
+            Self::Riscv64 => ArchKind::Riscv64,
+            Self::S390x => ArchKind::S390x,
+            Self::Sparc => ArchKind::Sparc,
+            Self::Sparc64 => ArchKind::Sparc64,
+            Self::Wasm32 => ArchKind::Wasm32,
+            Self::Wasm64 => ArchKind::Wasm64,
+            Self::Unknown(_) => ArchKind::Unknown,
+        }
+    }
+
+    /// Get the name of this CPU architecture, without allocating.
+    ///
+    /// Unlike the [`Display`] implementation, the name of an `Unknown` variant
+    /// is not prefixed with "Unknown: ".
+    pub fn as_str(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.name())
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        match self {
             Self::ArmV5 => "armv5",
             Self::ArmV6 => "armv6",
             Self::ArmV7 => "armv7",
@@ -105,12 +366,44 @@ impl Display for Arch {
             Self::Wasm32 => "wasm32",
             Self::Wasm64 => "wasm64",
             Self::X64 => "x86_64",
-            Self::Unknown(arch) => arch,
-        })
+            Self::X32 => "x32",
+            Self::Unknown(arch) => arch.as_str(),
+        }
+    }
+
+    /// Get a friendly description of this CPU architecture, for showing to
+    /// users (such as in an about box).
+    ///
+    /// Unlike [`as_str()`](Arch::as_str), this isn't meant to be parsed.
+    /// `Unknown` architectures are described as "Unknown".
+    ///
+    /// Example: "64-bit Intel/AMD", or "Apple Silicon" for ARM64 on Apple
+    /// platforms
+    pub fn human_name(&self) -> &'static str {
+        match self {
+            Self::ArmV5 => "ARMv5",
+            Self::ArmV6 => "ARMv6",
+            Self::ArmV7 => "32-bit ARM",
+            Self::Arm64 if cfg!(target_vendor = "apple") => "Apple Silicon",
+            Self::Arm64 => "64-bit ARM",
+            Self::I386 | Self::I586 | Self::I686 => "32-bit Intel/AMD",
+            Self::X64 => "64-bit Intel/AMD",
+            Self::X32 => "64-bit Intel/AMD (x32)",
+            Self::Mips | Self::MipsEl => "32-bit MIPS",
+            Self::Mips64 | Self::Mips64El => "64-bit MIPS",
+            Self::PowerPc => "32-bit PowerPC",
+            Self::PowerPc64 | Self::PowerPc64Le => "64-bit PowerPC",
+            Self::Riscv32 => "32-bit RISC-V",
+            Self::Riscv64 => "64-bit RISC-V",
+            Self::S390x => "IBM Z",
+            Self::Sparc => "32-bit SPARC",
+            Self::Sparc64 => "64-bit SPARC",
+            Self::Wasm32 => "32-bit WebAssembly",
+            Self::Wasm64 => "64-bit WebAssembly",
+            Self::Unknown(_) => "Unknown",
+        }
     }
-}
 
-impl Arch {
     /// Get the width of this architecture.
     pub fn width(&self) -> Result<Width> {
         match self {
@@ -125,7 +418,8 @@ impl Arch {
             | Arch::PowerPc
             | Arch::Riscv32
             | Arch::Sparc
-            | Arch::Wasm32 => Ok(Width::Bits32),
+            | Arch::Wasm32
+            | Arch::X32 => Ok(Width::Bits32),
             Arch::Arm64
             | Arch::Mips64
             | Arch::Mips64El
@@ -146,3 +440,41 @@ impl Arch {
         }
     }
 }
+
+/// An `Unknown` architecture named "Unknown".
+impl Default for Arch {
+    fn default() -> Self {
+        Self::Unknown("Unknown".to_string())
+    }
+}
+
+impl AsRef<str> for Arch {
+    fn as_ref(&self) -> &str {
+        self.name()
+    }
+}
+
+impl From<Arch> for String {
+    fn from(value: Arch) -> Self {
+        match value {
+            Arch::Unknown(name) => name,
+            known => known.name().to_string(),
+        }
+    }
+}
+
+/// Case-insensitive comparison with the name returned by
+/// [`as_str()`](Arch::as_str).
+impl PartialEq<str> for Arch {
+    fn eq(&self, other: &str) -> bool {
+        self.name().eq_ignore_ascii_case(other)
+    }
+}
+
+/// Case-insensitive comparison with the name returned by
+/// [`as_str()`](Arch::as_str).
+impl PartialEq<&str> for Arch {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
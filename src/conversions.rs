@@ -1,3 +1,8 @@
+#[cfg(any(
+    all(not(target_os = "windows"), not(target_arch = "wasm32")),
+    all(target_arch = "wasm32", target_os = "wasi"),
+))]
+use std::env;
 use std::{
     ffi::OsString,
     io::{Error, ErrorKind},
@@ -30,3 +35,61 @@ pub(crate) fn string_from_os(string: OsString) -> Result<String> {
         })
     }
 }
+
+/// Like [`string_from_os()`], but on unix-systems, decodes strings that aren't
+/// valid UTF-8 as ISO-8859-1 if that's the character set of the locale (as
+/// for GECOS fields on some legacy systems).
+pub(crate) fn string_from_os_locale(string: OsString) -> Result<String> {
+    #[cfg(any(
+        all(not(target_os = "windows"), not(target_arch = "wasm32")),
+        all(target_arch = "wasm32", target_os = "wasi"),
+    ))]
+    {
+        #[cfg(not(target_os = "wasi"))]
+        use std::os::unix::ffi::OsStringExt;
+        #[cfg(target_os = "wasi")]
+        use std::os::wasi::ffi::OsStringExt;
+
+        String::from_utf8(string.into_vec()).or_else(|e| {
+            if !latin1_locale() {
+                return Err(Error::new(ErrorKind::InvalidData, e));
+            }
+
+            // ISO-8859-1 bytes are the first 256 Unicode code points
+            Ok(e.as_bytes().iter().map(|&b| char::from(b)).collect())
+        })
+    }
+
+    #[cfg(any(
+        target_os = "windows",
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+    ))]
+    {
+        string_from_os(string)
+    }
+}
+
+/// Returns true if the codeset of the `LC_CTYPE` locale is ISO-8859-1.
+#[cfg(any(
+    all(not(target_os = "windows"), not(target_arch = "wasm32")),
+    all(target_arch = "wasm32", target_os = "wasi"),
+))]
+fn latin1_locale() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|locale| !locale.is_empty())
+        .unwrap_or_default();
+    // Example: "de_DE.ISO-8859-1" or "fr_FR.iso88591@euro"
+    let codeset = locale
+        .split('@')
+        .next()
+        .unwrap_or_default()
+        .splitn(2, '.')
+        .nth(1)
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+        .replace(|c| c == '-' || c == '_', "");
+
+    codeset == "iso88591" || codeset == "latin1"
+}
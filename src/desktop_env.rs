@@ -1,9 +1,12 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
 
 // FIXME: V2: Move `Unknown` variants to the top of the enum.
 
 /// The desktop environment of a system
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 #[non_exhaustive]
 pub enum DesktopEnv {
     /// Popular GTK-based desktop environment on Linux
@@ -41,17 +44,158 @@ pub enum DesktopEnv {
     Ermine,
     /// Default desktop environment for Redox
     Orbital,
+    /// System76's desktop environment for Pop!_OS
+    Cosmic,
+    /// Default desktop environment for elementary OS
+    Pantheon,
+    /// Common Desktop Environment, the Motif-based desktop of commercial Unix
+    Cde,
+    /// Tab Window Manager, the default window manager of the X Window System
+    Twm,
+    /// F Virtual Window Manager for X Windows
+    Fvwm,
+    /// No graphical session (no display server is available)
+    Headless,
     /// Unknown desktop environment
     Unknown(String),
 }
 
+/// The kind of a [`DesktopEnv`], without the payload of its `Unknown` variant.
+///
+/// Each variant corresponds to the [`DesktopEnv`] variant of the same name.
+/// Unlike [`DesktopEnv`], this is [`Copy`].
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[non_exhaustive]
+pub enum DesktopEnvKind {
+    Gnome,
+    Windows,
+    Lxde,
+    Openbox,
+    Mate,
+    Xfce,
+    Kde,
+    Cinnamon,
+    I3,
+    Aqua,
+    Ios,
+    Android,
+    WebBrowser,
+    Console,
+    Ubuntu,
+    Ermine,
+    Orbital,
+    Cosmic,
+    Pantheon,
+    Cde,
+    Twm,
+    Fvwm,
+    Headless,
+    Unknown,
+}
+
 impl Display for DesktopEnv {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         if let Self::Unknown(_) = self {
             f.write_str("Unknown: ")?;
         }
 
-        f.write_str(match self {
+        f.write_str(self.name())
+    }
+}
+
+impl DesktopEnv {
+    /// Get the [`DesktopEnvKind`] of this desktop environment.
+    pub fn kind(&self) -> DesktopEnvKind {
+        match self {
+            Self::Gnome => DesktopEnvKind::Gnome,
+            Self::Windows => DesktopEnvKind::Windows,
+            Self::Lxde => DesktopEnvKind::Lxde,
+            Self::Openbox => DesktopEnvKind::Openbox,
+            Self::Mate => DesktopEnvKind::Mate,
+            Self::Xfce => DesktopEnvKind::Xfce,
+            Self::Kde => DesktopEnvKind::Kde,
+            Self::Cinnamon => DesktopEnvKind::Cinnamon,
+            Self::I3 => DesktopEnvKind::I3,
+            Self::Aqua => DesktopEnvKind::Aqua,
+            Self::Ios => DesktopEnvKind::Ios,
+            Self::Android => DesktopEnvKind::Android,
+            Self::WebBrowser => DesktopEnvKind::WebBrowser,
+            Self::Console => DesktopEnvKind::Console,
+            Self::Ubuntu => DesktopEnvKind::Ubuntu,
+            Self::Ermine => DesktopEnvKind::Ermine,
+            Self::Orbital => DesktopEnvKind::Orbital,
+            Self::Cosmic => DesktopEnvKind::Cosmic,
+            Self::Pantheon => DesktopEnvKind::Pantheon,
+            Self::Cde => DesktopEnvKind::Cde,
+            Self::Twm => DesktopEnvKind::Twm,
+            Self::Fvwm => DesktopEnvKind::Fvwm,
+            Self::Headless => DesktopEnvKind::Headless,
+            Self::Unknown(_) => DesktopEnvKind::Unknown,
+        }
+    }
+
+    /// Get the name of this desktop environment, without allocating.
+    ///
+    /// Unlike the [`Display`] implementation, the name of an `Unknown` variant
+    /// is not prefixed with "Unknown: ".
+    pub fn as_str(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.name())
+    }
+
+    /// Get a stable, lowercase identifier for this desktop environment, for
+    /// use in logs, metrics and scripts.
+    ///
+    /// Unlike the [`Display`] implementation and [`as_str()`](Self::as_str),
+    /// these identifiers won't change between releases.  They only contain
+    /// ASCII lowercase letters, digits and dashes; for `Unknown` desktop
+    /// environments, the name is lowercased and any other characters are
+    /// replaced with dashes.
+    ///
+    /// Example: "gnome", "kde", "xfce" or "web-browser"
+    pub fn short_name(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            Self::Gnome => "gnome",
+            Self::Windows => "windows",
+            Self::Lxde => "lxde",
+            Self::Openbox => "openbox",
+            Self::Mate => "mate",
+            Self::Xfce => "xfce",
+            Self::Kde => "kde",
+            Self::Cinnamon => "cinnamon",
+            Self::I3 => "i3",
+            Self::Aqua => "aqua",
+            Self::Ios => "ios",
+            Self::Android => "android",
+            Self::WebBrowser => "web-browser",
+            Self::Console => "console",
+            Self::Ubuntu => "ubuntu",
+            Self::Ermine => "ermine",
+            Self::Orbital => "orbital",
+            Self::Cosmic => "cosmic",
+            Self::Pantheon => "pantheon",
+            Self::Cde => "cde",
+            Self::Twm => "twm",
+            Self::Fvwm => "fvwm",
+            Self::Headless => "headless",
+            Self::Unknown(name) => {
+                return Cow::Owned(
+                    name.chars()
+                        .map(|c| {
+                            if c.is_ascii_alphanumeric() {
+                                c.to_ascii_lowercase()
+                            } else {
+                                '-'
+                            }
+                        })
+                        .collect(),
+                )
+            }
+        })
+    }
+
+    fn name(&self) -> &str {
+        match self {
             Self::Gnome => "Gnome",
             Self::Windows => "Windows",
             Self::Lxde => "LXDE",
@@ -69,24 +213,97 @@ impl Display for DesktopEnv {
             Self::Ubuntu => "Ubuntu",
             Self::Ermine => "Ermine",
             Self::Orbital => "Orbital",
-            Self::Unknown(a) => a,
-        })
+            Self::Cosmic => "COSMIC",
+            Self::Pantheon => "Pantheon",
+            Self::Cde => "CDE",
+            Self::Twm => "TWM",
+            Self::Fvwm => "FVWM",
+            Self::Headless => "Headless",
+            Self::Unknown(a) => a.as_str(),
+        }
     }
-}
 
-impl DesktopEnv {
     /// Returns true if the desktop environment is based on GTK.
+    #[deprecated(note = "use `is_gtk_based()` instead")]
     pub fn is_gtk(&self) -> bool {
-        *self == Self::Gnome
-            || *self == Self::Ubuntu
-            || *self == Self::Cinnamon
-            || *self == Self::Lxde
-            || *self == Self::Mate
-            || *self == Self::Xfce
+        self.is_gtk_based()
     }
 
     /// Returns true if the desktop environment is based on KDE.
+    #[deprecated(note = "use `is_qt_based()` instead")]
     pub fn is_kde(&self) -> bool {
-        *self == Self::Kde
+        self.is_qt_based()
+    }
+
+    /// Returns true if the desktop environment's native toolkit is GTK.
+    ///
+    /// These are GNOME, Ubuntu, Cinnamon, LXDE, MATE, Xfce and Pantheon, as
+    /// well as Budgie and Unity (which don't have their own variant).
+    pub fn is_gtk_based(&self) -> bool {
+        match self {
+            Self::Gnome
+            | Self::Ubuntu
+            | Self::Cinnamon
+            | Self::Lxde
+            | Self::Mate
+            | Self::Xfce
+            | Self::Pantheon => true,
+            Self::Unknown(name) => ["Budgie", "Unity"]
+                .iter()
+                .any(|gtk| name.eq_ignore_ascii_case(gtk)),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the desktop environment's native toolkit is Qt.
+    ///
+    /// These are KDE Plasma, as well as LXQt, Deepin, UKUI and Lumina (which
+    /// don't have their own variant).
+    pub fn is_qt_based(&self) -> bool {
+        match self {
+            Self::Kde => true,
+            Self::Unknown(name) => ["LXQt", "Deepin", "DDE", "UKUI", "Lumina"]
+                .iter()
+                .any(|qt| name.eq_ignore_ascii_case(qt)),
+            _ => false,
+        }
+    }
+}
+
+/// An `Unknown` desktop environment named "Unknown".
+impl Default for DesktopEnv {
+    fn default() -> Self {
+        Self::Unknown("Unknown".to_string())
+    }
+}
+
+impl AsRef<str> for DesktopEnv {
+    fn as_ref(&self) -> &str {
+        self.name()
+    }
+}
+
+impl From<DesktopEnv> for String {
+    fn from(value: DesktopEnv) -> Self {
+        match value {
+            DesktopEnv::Unknown(name) => name,
+            known => known.name().to_string(),
+        }
+    }
+}
+
+/// Case-insensitive comparison with the name returned by
+/// [`as_str()`](DesktopEnv::as_str).
+impl PartialEq<str> for DesktopEnv {
+    fn eq(&self, other: &str) -> bool {
+        self.name().eq_ignore_ascii_case(other)
+    }
+}
+
+/// Case-insensitive comparison with the name returned by
+/// [`as_str()`](DesktopEnv::as_str).
+impl PartialEq<&str> for DesktopEnv {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
     }
 }
@@ -4,12 +4,17 @@
 //! "localhost" on error.  This might not be desirable in some situations.  The
 //! functions in this module all return a [`Result`].
 
-use std::ffi::OsString;
+#[cfg(feature = "idna")]
+use std::io::Error;
+use std::{
+    ffi::OsString, io::ErrorKind, net::IpAddr, path::Path, time::SystemTime,
+};
 
 use crate::{
     conversions,
-    os::{Os, Target},
-    Result,
+    os::{self, Backend, Os, Target},
+    Arch, DesktopEnv, Language, LibcKind, MeasurementSystem, PackageFormat,
+    Passwd, Platform, Result,
 };
 
 /// Get the user's account name; usually just the username, but may include an
@@ -17,6 +22,9 @@ use crate::{
 ///
 /// If you don't want the account server hostname, use [`username()`].
 ///
+/// On Linux machines joined to a domain (such as Active Directory through
+/// SSSD), the username is qualified with the Kerberos default realm.
+///
 /// Example: `username@example.com`
 #[inline(always)]
 pub fn account() -> Result<String> {
@@ -28,19 +36,31 @@ pub fn account() -> Result<String> {
 ///
 /// If you don't want the account server hostname, use [`username()`].
 ///
+/// On Linux machines joined to a domain (such as Active Directory through
+/// SSSD), the username is qualified with the Kerberos default realm.
+///
 /// Example: `username@example.com`
 #[inline(always)]
 pub fn account_os() -> Result<OsString> {
-    Target::account(Os)
+    Backend::account(Os)
 }
 
 /// Get the user's username.
 ///
 /// On unix-systems this differs from [`realname()`] most notably in that spaces
-/// are not allowed in the username.
+/// are not allowed in the username.  If the user has no passwd entry, this
+/// falls back to the `LOGNAME` or `USER` environment variables.
 #[inline(always)]
 pub fn username() -> Result<String> {
-    username_os().and_then(conversions::string_from_os)
+    username_with(Os)
+}
+
+/// Get the user's username from a custom [`Target`].
+///
+/// Same as [`username()`] for [`Os`].
+#[inline(always)]
+pub fn username_with<T: Target>(target: T) -> Result<String> {
+    target.username().and_then(conversions::string_from_os)
 }
 
 /// Get the user's username.
@@ -53,9 +73,22 @@ pub fn username_os() -> Result<OsString> {
 }
 
 /// Get the user's real (full) name.
+///
+/// On unix-systems, a name that isn't valid UTF-8 is decoded as ISO-8859-1
+/// (Latin-1) if that's the character set of the `LC_CTYPE` locale.
 #[inline(always)]
 pub fn realname() -> Result<String> {
-    realname_os().and_then(conversions::string_from_os)
+    realname_with(Os)
+}
+
+/// Get the user's real (full) name from a custom [`Target`].
+///
+/// Same as [`realname()`] for [`Os`].
+#[inline(always)]
+pub fn realname_with<T: Target>(target: T) -> Result<String> {
+    target
+        .realname()
+        .and_then(conversions::string_from_os_locale)
 }
 
 /// Get the user's real (full) name.
@@ -64,12 +97,231 @@ pub fn realname_os() -> Result<OsString> {
     Target::realname(Os)
 }
 
+/// Get the user's real (full) name, if the operating system provides one.
+///
+/// Unlike [`whoami::realname()`](crate::realname()), this never generates a
+/// name from the username; returns `Ok(None)` if no real name is set.
+#[inline(always)]
+pub fn realname_checked() -> Result<Option<String>> {
+    match realname() {
+        Ok(name) if name.is_empty() => Ok(None),
+        Ok(name) => Ok(Some(name)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Get all of the comma-separated fields of the user's GECOS (passwd comment)
+/// field.
+///
+/// The first field is the full name returned by [`realname()`], which is
+/// conventionally followed by the office, work phone and home phone.  Only
+/// supported on unix-systems.
+///
+/// Example: `["Jane Doe", "Room 101", "x1234", ""]`
+#[inline(always)]
+pub fn gecos() -> Result<Vec<String>> {
+    let gecos = conversions::string_from_os_locale(Backend::gecos(Os)?)?;
+
+    Ok(gecos.split(',').map(ToString::to_string).collect())
+}
+
+/// Get the user's given name and family name, separately.
+///
+/// This lets callers assemble the name in their preferred order, regardless
+/// of the order [`realname()`] uses (which may be "Family, Given" on Windows
+/// domain accounts).  Only supported on Windows.
+///
+/// Example: `("Jane", "Doe")`
+#[inline(always)]
+pub fn realname_given_family() -> Result<(String, String)> {
+    let (given, family) = Backend::realname_given_family(Os)?;
+
+    Ok((
+        conversions::string_from_os(given)?,
+        conversions::string_from_os(family)?,
+    ))
+}
+
+/// Get the name of the hypervisor the operating system is running under, or
+/// `None` on bare metal.
+///
+/// Supported on Linux (from DMI system information and CPUID) and on x86
+/// Windows (from CPUID).  On Windows with virtualization-based security
+/// enabled, this may report "Hyper-V" even on bare metal.
+///
+/// Example: "KVM", "VMware", "Hyper-V", "Xen" or "VirtualBox"
+#[inline(always)]
+pub fn hypervisor() -> Result<Option<String>> {
+    Backend::hypervisor(Os)
+}
+
+/// Get the full name of the user with the given username.
+///
+/// Returns an error with [`ErrorKind::NotFound`] if the user doesn't exist.
+#[inline(always)]
+pub fn realname_of(username: &str) -> Result<String> {
+    Backend::realname_of(Os, username)
+        .and_then(conversions::string_from_os_locale)
+}
+
+/// Get the home directory of the user with the given username.
+///
+/// Returns an error with [`ErrorKind::NotFound`] if the user doesn't exist.
+#[inline(always)]
+pub fn home_dir_of(username: &str) -> Result<String> {
+    Backend::home_dir_of(Os, username).and_then(conversions::string_from_os)
+}
+
+/// Get the current user's account record (username, real name, user and group
+/// IDs, home directory and shell) with a single lookup.
+///
+/// Prefer this over calling [`username()`], [`realname()`] and others
+/// separately when more than one field is needed, since each of those does its
+/// own lookup.  Only supported on unix-systems.
+#[inline(always)]
+pub fn passwd() -> Result<Passwd> {
+    Backend::passwd(Os)
+}
+
+/// Get the name of the user's primary group.
+///
+/// On unix-systems this is the group matching the group ID in the user's
+/// passwd entry.
+#[inline(always)]
+pub fn primary_group() -> Result<String> {
+    primary_group_os().and_then(conversions::string_from_os)
+}
+
+/// Get the name of the user's primary group.
+///
+/// On unix-systems this is the group matching the group ID in the user's
+/// passwd entry.
+#[inline(always)]
+pub fn primary_group_os() -> Result<OsString> {
+    Backend::primary_group(Os)
+}
+
+/// Get the CPU Architecture.
+#[inline(always)]
+pub fn arch() -> Result<Arch> {
+    arch_with(Os)
+}
+
+/// Get the CPU Architecture from a custom [`Target`].
+#[inline(always)]
+pub fn arch_with<T: Target>(target: T) -> Result<Arch> {
+    target.arch()
+}
+
 /// Get the name of the operating system distribution and (possibly) version.
 ///
 /// Example: "Windows 10" or "Fedora 26 (Workstation Edition)"
 #[inline(always)]
 pub fn distro() -> Result<String> {
-    Target::distro(Os)
+    distro_with(Os)
+}
+
+/// Get the name of the operating system distribution and (possibly) version
+/// from a custom [`Target`].
+#[inline(always)]
+pub fn distro_with<T: Target>(target: T) -> Result<String> {
+    target.distro()
+}
+
+/// Get the name of the operating system distribution and (possibly) version
+/// from an [os-release] file at an arbitrary path, such as one extracted from
+/// a container image.
+///
+/// This uses the same parser as [`distro()`] does for `/etc/os-release`.
+///
+/// [os-release]: https://www.freedesktop.org/software/systemd/man/os-release.html
+#[inline(always)]
+pub fn distro_from_path(path: &Path) -> Result<String> {
+    os::distro_from_path(path)
+}
+
+/// Get the machine-readable identifier of the operating system distribution.
+///
+/// Lowercase with no spaces, suitable for use in scripts and for selecting
+/// distribution-specific behavior.
+///
+/// Example: "fedora" or "ubuntu"
+#[inline(always)]
+pub fn distro_id() -> Result<String> {
+    Backend::distro_id(Os)
+}
+
+/// Get the machine-readable identifiers of the operating system distributions
+/// that this one is derived from or closely related to, most closely related
+/// first.
+///
+/// Empty if the distribution isn't derived from another.
+///
+/// Example: `["debian"]` on Ubuntu, or `["rhel", "centos", "fedora"]` on Rocky
+/// Linux
+#[inline(always)]
+pub fn distro_id_like() -> Result<Vec<String>> {
+    Backend::distro_id_like(Os)
+}
+
+/// Get the native package format of the operating system, for choosing which
+/// installer to offer.
+///
+/// On Linux, this is derived from [`distro_id()`] and [`distro_id_like()`]
+/// (Debian-like is `deb`, Fedora, RHEL and SUSE-like is `rpm`, Arch-like is
+/// `pkg.tar.zst`), or is `flatpak` inside a Flatpak sandbox.  Mac OS is `pkg`,
+/// and Windows is `msi`.  Returns [`PackageFormat::Unknown`] when unclear.
+pub fn package_format() -> Result<PackageFormat> {
+    match Target::platform(Os)? {
+        Platform::Windows => return Ok(PackageFormat::Msi),
+        Platform::MacOS => return Ok(PackageFormat::Pkg),
+        Platform::Linux => {}
+        _ => return Ok(PackageFormat::Unknown),
+    }
+
+    if Path::new("/.flatpak-info").exists() {
+        return Ok(PackageFormat::Flatpak);
+    }
+
+    let mut ids = vec![distro_id()?];
+
+    ids.extend(distro_id_like()?);
+
+    Ok(ids
+        .iter()
+        .find_map(|id| {
+            Some(match id.as_str() {
+                "debian" | "ubuntu" => PackageFormat::Deb,
+                "fedora" | "rhel" | "centos" | "suse" | "opensuse"
+                | "mageia" => PackageFormat::Rpm,
+                "arch" => PackageFormat::Pacman,
+                "alpine" => PackageFormat::Apk,
+                _ => return None,
+            })
+        })
+        .unwrap_or(PackageFormat::Unknown))
+}
+
+/// Get the release codename of the operating system distribution, or `None` if
+/// it doesn't have one.
+///
+/// Read from the `VERSION_CODENAME` field of `/etc/os-release`.
+///
+/// Example: "jammy" on Ubuntu 22.04, or "bookworm" on Debian 12
+#[inline(always)]
+pub fn distro_codename() -> Result<Option<String>> {
+    Backend::distro_codename(Os)
+}
+
+/// Get the `(major, minor, build)` version numbers of the operating system.
+///
+/// Only supported on Windows, where the numbers come from `RtlGetVersion()`.
+///
+/// Example: `(10, 0, 19045)` on Windows 10 22H2
+#[inline(always)]
+pub fn os_version_raw() -> Result<(u32, u32, u32)> {
+    Backend::os_version_raw(Os)
 }
 
 /// Get the device name (also known as "Pretty Name").
@@ -77,7 +329,15 @@ pub fn distro() -> Result<String> {
 /// Often used to identify device for bluetooth pairing.
 #[inline(always)]
 pub fn devicename() -> Result<String> {
-    devicename_os().and_then(conversions::string_from_os)
+    devicename_with(Os)
+}
+
+/// Get the device name (also known as "Pretty Name") from a custom [`Target`].
+///
+/// Same as [`devicename()`] for [`Os`].
+#[inline(always)]
+pub fn devicename_with<T: Target>(target: T) -> Result<String> {
+    target.devicename().and_then(conversions::string_from_os)
 }
 
 /// Get the device name (also known as "Pretty Name").
@@ -88,12 +348,325 @@ pub fn devicename_os() -> Result<OsString> {
     Target::devicename(Os)
 }
 
+/// Get the hardware model identifier of the device.
+///
+/// On Apple platforms the identifier's prefix describes the kind of device
+/// (`iPhone`, `iPad`, `MacBookPro`, etc.).
+///
+/// Example: "iPhone14,2" or "MacBookPro18,3"
+#[inline(always)]
+pub fn device_model() -> Result<String> {
+    device_model_os().and_then(conversions::string_from_os)
+}
+
+/// Get the hardware model identifier of the device.
+///
+/// On Apple platforms the identifier's prefix describes the kind of device
+/// (`iPhone`, `iPad`, `MacBookPro`, etc.).
+///
+/// Example: "iPhone14,2" or "MacBookPro18,3"
+#[inline(always)]
+pub fn device_model_os() -> Result<OsString> {
+    Backend::device_model(Os)
+}
+
 /// Get the host device's hostname.
 ///
 /// Limited to a-z, A-Z, 0-9, and dashes.  This limit also applies to
 /// [`devicename()`] when targeting Windows.  Usually hostnames are
 /// case-insensitive, but it's not a hard requirement.
+///
+/// The case is preserved, so unlike the deprecated
+/// [`hostname()`](crate::hostname), this is not normalized to lowercase.  On
+/// Windows, this is the NetBIOS name, which is usually all uppercase.  Use
+/// [`str::make_ascii_lowercase()`] on the result if you need a hostname that
+/// compares the same across platforms (for example, as a cache key).
 #[inline(always)]
 pub fn hostname() -> Result<String> {
-    Target::hostname(Os)
+    hostname_with(Os)
+}
+
+/// Get the host device's hostname from a custom [`Target`].
+#[inline(always)]
+pub fn hostname_with<T: Target>(target: T) -> Result<String> {
+    target.hostname()
+}
+
+/// Get the host device's hostname, without its domain.
+///
+/// Unlike [`hostname()`], which may return a fully qualified domain name on
+/// some systems, this is truncated at the first dot.  IP address literals
+/// (IPv4, or IPv6 in brackets or not) are returned unchanged.
+///
+/// Example: "web01" for "web01.prod.example.com"
+pub fn hostname_short() -> Result<String> {
+    let mut hostname = hostname()?;
+    let address = hostname.trim_start_matches('[').trim_end_matches(']');
+
+    if address.parse::<IpAddr>().is_err() {
+        if let Some(dot) = hostname.find('.') {
+            hostname.truncate(dot);
+        }
+    }
+
+    Ok(hostname)
+}
+
+/// Get the host device's hostname, converted to its ASCII-compatible
+/// (punycode) form if it's internationalized.
+///
+/// ASCII hostnames are returned unchanged.  Requires the `idna` feature.
+///
+/// Example: "xn--caf-dma" for "café"
+#[cfg(feature = "idna")]
+pub fn hostname_ascii() -> Result<String> {
+    let hostname = hostname()?;
+
+    if hostname.is_ascii() {
+        return Ok(hostname);
+    }
+
+    idna::domain_to_ascii(&hostname)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+/// Append the user's username to `buf`, reusing its allocation.
+///
+/// Equivalent to pushing the result of [`username()`], but on unix-systems
+/// the name is copied directly into `buf` without an intermediate `String`.
+/// `buf` is left unchanged on error.
+#[inline(always)]
+pub fn username_into(buf: &mut String) -> Result<()> {
+    Backend::username_into(Os, buf)
+}
+
+/// Append the host name to `buf`, reusing its allocation.
+///
+/// Equivalent to pushing the result of [`hostname()`], but on unix-systems
+/// the name is copied directly into `buf` without an intermediate `String`.
+/// `buf` is left unchanged on error.
+#[inline(always)]
+pub fn hostname_into(buf: &mut String) -> Result<()> {
+    Backend::hostname_into(Os, buf)
+}
+
+/// Append the device name to `buf`, reusing its allocation.
+///
+/// Equivalent to pushing the result of [`devicename()`].  `buf` is left
+/// unchanged on error.
+#[inline(always)]
+pub fn devicename_into(buf: &mut String) -> Result<()> {
+    Backend::devicename_into(Os, buf)
+}
+
+/// Get the CPU architecture of the machine, even when running under emulation.
+///
+/// Unlike [`arch()`], this detects x86_64 programs running under emulation on
+/// ARM64 (on Mac OS with Rosetta 2, and on Windows 11 on ARM).  Elsewhere, it
+/// is the same as [`arch()`].
+#[inline(always)]
+pub fn arch_native() -> Result<Arch> {
+    Backend::arch_native(Os)
+}
+
+/// Get the CPU architecture the hardware is capable of running.
+///
+/// Unlike [`arch_native()`], on Linux this detects 64-bit capable CPUs running
+/// a 32-bit kernel (ARMv8 CPUs, and x86 CPUs with long mode), so programs can
+/// suggest a 64-bit build.  Elsewhere, it is the same as [`arch_native()`].
+#[inline(always)]
+pub fn arch_hardware() -> Result<Arch> {
+    Backend::arch_hardware(Os)
+}
+
+/// Get the time the system was booted, to the nearest second.
+///
+/// Supported on Linux, Mac OS, iOS, FreeBSD, DragonFly BSD, NetBSD and
+/// Windows.
+#[inline(always)]
+pub fn boot_time() -> Result<SystemTime> {
+    Backend::boot_time(Os)
+}
+
+/// Get the kind of C library used by the operating system.
+///
+/// On Linux, this detects the dynamic loader installed by glibc or musl (such
+/// as on Alpine Linux), falling back to the C library this program is linked
+/// to.  Returns an error on other platforms.
+#[inline(always)]
+pub fn libc_kind() -> Result<LibcKind> {
+    Backend::libc_kind(Os)
+}
+
+/// Get the user's preferred system of units of measurement.
+///
+/// On Unix (other than macOS and iOS), this is derived from the territory of
+/// the `LC_ALL`, `LC_MEASUREMENT` or `LANG` locale environment variables.
+#[inline(always)]
+pub fn measurement_system() -> Result<MeasurementSystem> {
+    Backend::measurement_system(Os)
+}
+
+/// Get the user's preferred decimal separator for formatting numbers.
+///
+/// Example: `'.'` for English (US), or `','` for German (Germany)
+#[inline(always)]
+pub fn decimal_separator() -> Result<char> {
+    Backend::decimal_separator(Os)
+}
+
+/// Get the name and major version of the web browser, when running in one.
+///
+/// Example: `("Chrome", "124")`
+#[inline(always)]
+pub fn browser() -> Result<(String, String)> {
+    Backend::browser(Os)
+}
+
+/// Get the hostname configured for the host, read from `/etc/hostname`.
+///
+/// Unlike [`hostname()`], which is the running kernel's hostname, this is the
+/// name the host is configured to use on the next boot, which may differ (for
+/// example, on image-based systems where the running name is a placeholder).
+/// Supported on Linux and Redox.
+#[inline(always)]
+pub fn configured_hostname() -> Result<String> {
+    Backend::configured_hostname(Os)
+}
+
+/// Get the host device's node name, as reported by the kernel.
+///
+/// This is the `nodename` field of `uname()`, which usually matches
+/// [`hostname()`], but may differ from it (for example, after the host is
+/// renamed on the network).
+#[inline(always)]
+pub fn nodename() -> Result<String> {
+    Backend::nodename(Os)
+}
+
+/// Get the host device's fully qualified domain name.
+///
+/// Unlike [`hostname()`], which is usually the short name of the host, this
+/// includes the domain, as resolved by the system's name service.
+///
+/// Example: "build-01.example.com"
+#[inline(always)]
+pub fn fqdn() -> Result<String> {
+    Backend::fqdn(Os)
+}
+
+/// Get the number of logical CPU cores (hardware threads) available.
+#[inline(always)]
+pub fn cpu_cores() -> Result<usize> {
+    Backend::cpu_cores(Os)
+}
+
+/// Get the number of physical CPU cores.
+///
+/// This may be less than [`cpu_cores()`] on CPUs with simultaneous
+/// multithreading (hyper-threading).
+#[inline(always)]
+pub fn cpu_cores_physical() -> Result<usize> {
+    Backend::cpu_cores_physical(Os)
+}
+
+/// Get the total amount of physical memory (RAM) installed, in bytes.
+#[inline(always)]
+pub fn memory_total() -> Result<u64> {
+    Backend::memory_total(Os)
+}
+
+/// Get the desktop environment.
+///
+/// Unlike [`desktop_env()`](crate::desktop_env), returns an error with
+/// [`ErrorKind::NotFound`] when detection fails, rather than
+/// `DesktopEnv::Unknown("Unknown")`.  A system without a desktop is detected
+/// as [`DesktopEnv::Headless`], and a desktop without a matching variant as
+/// `DesktopEnv::Unknown` with its name.
+#[inline(always)]
+pub fn desktop_env() -> Result<DesktopEnv> {
+    desktop_env_with(Os)
+}
+
+/// Get the desktop environment from a custom [`Target`].
+#[inline(always)]
+pub fn desktop_env_with<T: Target>(target: T) -> Result<DesktopEnv> {
+    target.desktop_env()
+}
+
+/// Get the platform.
+///
+/// Unlike [`platform()`](crate::platform), returns an error with
+/// [`ErrorKind::NotFound`] when detection fails (as in a web browser without a
+/// user agent), rather than `Platform::Unknown("Unknown")`.
+#[inline(always)]
+pub fn platform() -> Result<Platform> {
+    platform_with(Os)
+}
+
+/// Get the platform from a custom [`Target`].
+#[inline(always)]
+pub fn platform_with<T: Target>(target: T) -> Result<Platform> {
+    target.platform()
+}
+
+/// Get the user's preferred languages from a custom [`Target`], most preferred
+/// first.
+///
+/// Unlike [`langs()`](crate::langs), there's no fallback when the target
+/// doesn't provide any.
+#[inline(always)]
+pub fn langs_with<T: Target>(target: T) -> Result<Vec<Language>> {
+    target.langs()
+}
+
+/// Get the version of the desktop environment.
+///
+/// Unlike [`desktop_env()`](crate::desktop_env), this may spawn a subprocess
+/// (such as `gnome-shell --version`) to query the version.  Supported for
+/// GNOME, KDE Plasma, Xfce, MATE and Cinnamon on Linux, BSD and illumos.
+///
+/// Example: "45.2"
+#[inline(always)]
+pub fn desktop_env_version() -> Result<String> {
+    Backend::desktop_env_version(Os)
+}
+
+/// Get the scale factor of the primary display, as a hint before initializing
+/// a GUI toolkit.
+///
+/// On Linux, BSD and illumos, this is read from the `GDK_SCALE` or
+/// `QT_SCALE_FACTOR` environment variables, or the `Xft.dpi` setting in
+/// `~/.Xresources`.  On Mac OS, this is the ratio of pixels to points of the
+/// main display.  On Windows 10 version 1607 and later, this is the system DPI
+/// divided by 96, which is always 1.0 for processes that aren't DPI aware.
+///
+/// Example: `2.0` on a "Retina" or "HiDPI" display
+#[inline(always)]
+pub fn display_scale() -> Result<f32> {
+    Backend::display_scale(Os)
+}
+
+/// Get the name of the audio server used by the user's session, or `None` if
+/// none is detected.
+///
+/// Detects PipeWire and PulseAudio from running processes and their sockets,
+/// falling back to ALSA when a sound card is present.  Only supported on
+/// Linux.
+///
+/// Example: "PipeWire", "PulseAudio" or "ALSA"
+#[inline(always)]
+pub fn audio_server() -> Result<Option<String>> {
+    Backend::audio_server(Os)
+}
+
+/// Get the name of the init system (service manager).
+///
+/// Only supported on Linux.  Returns an error if the init system isn't one of
+/// systemd, OpenRC, runit, s6, dinit or SysV init.
+///
+/// Example: "systemd", "openrc", "runit" or "sysvinit"
+#[inline(always)]
+pub fn init_system() -> Result<String> {
+    Backend::init_system(Os)
 }
@@ -1,5 +1,278 @@
 use std::fmt::{self, Display, Formatter};
 
+/// Every [`Country`], except [`Country::Any`], in order of country code
+const COUNTRIES: &[Country] = &[
+    Country::Ad,
+    Country::Ae,
+    Country::Af,
+    Country::Ag,
+    Country::Ai,
+    Country::Al,
+    Country::Am,
+    Country::Ao,
+    Country::Aq,
+    Country::Ar,
+    Country::As,
+    Country::At,
+    Country::Au,
+    Country::Aw,
+    Country::Ax,
+    Country::Az,
+    Country::Ba,
+    Country::Bb,
+    Country::Bd,
+    Country::Be,
+    Country::Bf,
+    Country::Bg,
+    Country::Bh,
+    Country::Bi,
+    Country::Bj,
+    Country::Bl,
+    Country::Bm,
+    Country::Bn,
+    Country::Bo,
+    Country::Bq,
+    Country::Br,
+    Country::Bs,
+    Country::Bt,
+    Country::Bv,
+    Country::Bw,
+    Country::By,
+    Country::Bz,
+    Country::Ca,
+    Country::Cc,
+    Country::Cd,
+    Country::Cf,
+    Country::Cg,
+    Country::Ch,
+    Country::Ci,
+    Country::Ck,
+    Country::Cl,
+    Country::Cm,
+    Country::Cn,
+    Country::Co,
+    Country::Cr,
+    Country::Cu,
+    Country::Cv,
+    Country::Cw,
+    Country::Cx,
+    Country::Cy,
+    Country::Cz,
+    Country::De,
+    Country::Dj,
+    Country::Dk,
+    Country::Dm,
+    Country::Do,
+    Country::Dz,
+    Country::Ec,
+    Country::Ee,
+    Country::Eg,
+    Country::Eh,
+    Country::Er,
+    Country::Es,
+    Country::Et,
+    Country::Fi,
+    Country::Fj,
+    Country::Fk,
+    Country::Fm,
+    Country::Fo,
+    Country::Fr,
+    Country::Ga,
+    Country::Gb,
+    Country::Gd,
+    Country::Ge,
+    Country::Gf,
+    Country::Gg,
+    Country::Gh,
+    Country::Gi,
+    Country::Gl,
+    Country::Gm,
+    Country::Gn,
+    Country::Gp,
+    Country::Gq,
+    Country::Gr,
+    Country::Gs,
+    Country::Gt,
+    Country::Gu,
+    Country::Gw,
+    Country::Gy,
+    Country::Hk,
+    Country::Hm,
+    Country::Hn,
+    Country::Hr,
+    Country::Ht,
+    Country::Hu,
+    Country::Id,
+    Country::Ie,
+    Country::Il,
+    Country::Im,
+    Country::In,
+    Country::Io,
+    Country::Iq,
+    Country::Ir,
+    Country::Is,
+    Country::It,
+    Country::Je,
+    Country::Jm,
+    Country::Jo,
+    Country::Jp,
+    Country::Ke,
+    Country::Kg,
+    Country::Kh,
+    Country::Ki,
+    Country::Km,
+    Country::Kn,
+    Country::Kp,
+    Country::Kr,
+    Country::Kw,
+    Country::Ky,
+    Country::Kz,
+    Country::La,
+    Country::Lb,
+    Country::Lc,
+    Country::Li,
+    Country::Lk,
+    Country::Lr,
+    Country::Ls,
+    Country::Lt,
+    Country::Lu,
+    Country::Lv,
+    Country::Ly,
+    Country::Ma,
+    Country::Mc,
+    Country::Md,
+    Country::Me,
+    Country::Mf,
+    Country::Mg,
+    Country::Mh,
+    Country::Mk,
+    Country::Ml,
+    Country::Mm,
+    Country::Mn,
+    Country::Mo,
+    Country::Mp,
+    Country::Mq,
+    Country::Mr,
+    Country::Ms,
+    Country::Mt,
+    Country::Mu,
+    Country::Mv,
+    Country::Mw,
+    Country::Mx,
+    Country::My,
+    Country::Mz,
+    Country::Na,
+    Country::Nc,
+    Country::Ne,
+    Country::Nf,
+    Country::Ng,
+    Country::Ni,
+    Country::Nl,
+    Country::No,
+    Country::Np,
+    Country::Nr,
+    Country::Nu,
+    Country::Nz,
+    Country::Om,
+    Country::Pa,
+    Country::Pe,
+    Country::Pf,
+    Country::Pg,
+    Country::Ph,
+    Country::Pk,
+    Country::Pl,
+    Country::Pm,
+    Country::Pn,
+    Country::Pr,
+    Country::Ps,
+    Country::Pt,
+    Country::Pw,
+    Country::Py,
+    Country::Qa,
+    Country::Re,
+    Country::Ro,
+    Country::Rs,
+    Country::Ru,
+    Country::Rw,
+    Country::Sa,
+    Country::Sb,
+    Country::Sc,
+    Country::Sd,
+    Country::Se,
+    Country::Sg,
+    Country::Sh,
+    Country::Si,
+    Country::Sj,
+    Country::Sk,
+    Country::Sl,
+    Country::Sm,
+    Country::Sn,
+    Country::So,
+    Country::Sr,
+    Country::Ss,
+    Country::St,
+    Country::Sv,
+    Country::Sx,
+    Country::Sy,
+    Country::Sz,
+    Country::Tc,
+    Country::Td,
+    Country::Tf,
+    Country::Tg,
+    Country::Th,
+    Country::Tj,
+    Country::Tk,
+    Country::Tl,
+    Country::Tm,
+    Country::Tn,
+    Country::To,
+    Country::Tr,
+    Country::Tt,
+    Country::Tv,
+    Country::Tw,
+    Country::Tz,
+    Country::Ua,
+    Country::Ug,
+    Country::Um,
+    Country::Us,
+    Country::Uy,
+    Country::Uz,
+    Country::Va,
+    Country::Vc,
+    Country::Ve,
+    Country::Vg,
+    Country::Vi,
+    Country::Vn,
+    Country::Vu,
+    Country::Wf,
+    Country::Ws,
+    Country::Ye,
+    Country::Yt,
+    Country::Za,
+    Country::Zm,
+    Country::Zw,
+];
+
+/// ISO 639-1 codes of every language returned by [`Language::all()`]
+const LANGUAGE_CODES: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az",
+    "ba", "be", "bg", "bh", "bi", "bm", "bn", "bo", "br", "bs", "ca", "ce",
+    "ch", "co", "cr", "cs", "cu", "cv", "cy", "da", "de", "dv", "dz", "ee",
+    "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr",
+    "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr",
+    "ht", "hu", "hy", "hz", "ia", "id", "ie", "ig", "ii", "ik", "io", "is",
+    "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj", "kk", "kl", "km", "kn",
+    "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln",
+    "lo", "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms",
+    "mt", "my", "na", "nb", "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv",
+    "ny", "oc", "oj", "om", "or", "os", "pa", "pi", "pl", "ps", "pt", "qu",
+    "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk",
+    "sl", "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta",
+    "te", "tg", "th", "ti", "tk", "tl", "tn", "to", "tr", "ts", "tt", "tw",
+    "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo", "wa", "wo", "xh", "yi",
+    "yo", "za", "zh", "zu",
+];
+
 /// Country code for a [`Language`] dialect
 ///
 /// Uses <https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2>
@@ -11,16 +284,1046 @@ pub enum Country {
     // FIXME: Add aliases up to 3-4 letters, but hidden
     /// Any dialect
     Any,
+    /// `AD`: Andorra
+    Ad,
+    /// `AE`: United Arab Emirates
+    Ae,
+    /// `AF`: Afghanistan
+    Af,
+    /// `AG`: Antigua and Barbuda
+    Ag,
+    /// `AI`: Anguilla
+    Ai,
+    /// `AL`: Albania
+    Al,
+    /// `AM`: Armenia
+    Am,
+    /// `AO`: Angola
+    Ao,
+    /// `AQ`: Antarctica
+    Aq,
+    /// `AR`: Argentina
+    Ar,
+    /// `AS`: American Samoa
+    As,
+    /// `AT`: Austria
+    At,
+    /// `AU`: Australia
+    Au,
+    /// `AW`: Aruba
+    Aw,
+    /// `AX`: Åland Islands
+    Ax,
+    /// `AZ`: Azerbaijan
+    Az,
+    /// `BA`: Bosnia and Herzegovina
+    Ba,
+    /// `BB`: Barbados
+    Bb,
+    /// `BD`: Bangladesh
+    Bd,
+    /// `BE`: Belgium
+    Be,
+    /// `BF`: Burkina Faso
+    Bf,
+    /// `BG`: Bulgaria
+    Bg,
+    /// `BH`: Bahrain
+    Bh,
+    /// `BI`: Burundi
+    Bi,
+    /// `BJ`: Benin
+    Bj,
+    /// `BL`: Saint Barthélemy
+    Bl,
+    /// `BM`: Bermuda
+    Bm,
+    /// `BN`: Brunei Darussalam
+    Bn,
+    /// `BO`: Bolivia
+    Bo,
+    /// `BQ`: Bonaire, Sint Eustatius and Saba
+    Bq,
+    /// `BR`: Brazil
+    Br,
+    /// `BS`: Bahamas
+    Bs,
+    /// `BT`: Bhutan
+    Bt,
+    /// `BV`: Bouvet Island
+    Bv,
+    /// `BW`: Botswana
+    Bw,
+    /// `BY`: Belarus
+    By,
+    /// `BZ`: Belize
+    Bz,
+    /// `CA`: Canada
+    Ca,
+    /// `CC`: Cocos (Keeling) Islands
+    Cc,
+    /// `CD`: Congo, The Democratic Republic of the
+    Cd,
+    /// `CF`: Central African Republic
+    Cf,
+    /// `CG`: Congo
+    Cg,
+    /// `CH`: Switzerland
+    Ch,
+    /// `CI`: Côte d'Ivoire
+    Ci,
+    /// `CK`: Cook Islands
+    Ck,
+    /// `CL`: Chile
+    Cl,
+    /// `CM`: Cameroon
+    Cm,
+    /// `CN`: China
+    Cn,
+    /// `CO`: Colombia
+    Co,
+    /// `CR`: Costa Rica
+    Cr,
+    /// `CU`: Cuba
+    Cu,
+    /// `CV`: Cabo Verde
+    Cv,
+    /// `CW`: Curaçao
+    Cw,
+    /// `CX`: Christmas Island
+    Cx,
+    /// `CY`: Cyprus
+    Cy,
+    /// `CZ`: Czechia
+    Cz,
+    /// `DE`: Germany
+    De,
+    /// `DJ`: Djibouti
+    Dj,
+    /// `DK`: Denmark
+    Dk,
+    /// `DM`: Dominica
+    Dm,
+    /// `DO`: Dominican Republic
+    Do,
+    /// `DZ`: Algeria
+    Dz,
+    /// `EC`: Ecuador
+    Ec,
+    /// `EE`: Estonia
+    Ee,
+    /// `EG`: Egypt
+    Eg,
+    /// `EH`: Western Sahara
+    Eh,
+    /// `ER`: Eritrea
+    Er,
+    /// `ES`: Spain
+    Es,
+    /// `ET`: Ethiopia
+    Et,
+    /// `FI`: Finland
+    Fi,
+    /// `FJ`: Fiji
+    Fj,
+    /// `FK`: Falkland Islands (Malvinas)
+    Fk,
+    /// `FM`: Micronesia, Federated States of
+    Fm,
+    /// `FO`: Faroe Islands
+    Fo,
+    /// `FR`: France
+    Fr,
+    /// `GA`: Gabon
+    Ga,
+    /// `GB`: United Kingdom
+    Gb,
+    /// `GD`: Grenada
+    Gd,
+    /// `GE`: Georgia
+    Ge,
+    /// `GF`: French Guiana
+    Gf,
+    /// `GG`: Guernsey
+    Gg,
+    /// `GH`: Ghana
+    Gh,
+    /// `GI`: Gibraltar
+    Gi,
+    /// `GL`: Greenland
+    Gl,
+    /// `GM`: Gambia
+    Gm,
+    /// `GN`: Guinea
+    Gn,
+    /// `GP`: Guadeloupe
+    Gp,
+    /// `GQ`: Equatorial Guinea
+    Gq,
+    /// `GR`: Greece
+    Gr,
+    /// `GS`: South Georgia and the South Sandwich Islands
+    Gs,
+    /// `GT`: Guatemala
+    Gt,
+    /// `GU`: Guam
+    Gu,
+    /// `GW`: Guinea-Bissau
+    Gw,
+    /// `GY`: Guyana
+    Gy,
+    /// `HK`: Hong Kong
+    Hk,
+    /// `HM`: Heard Island and McDonald Islands
+    Hm,
+    /// `HN`: Honduras
+    Hn,
+    /// `HR`: Croatia
+    Hr,
+    /// `HT`: Haiti
+    Ht,
+    /// `HU`: Hungary
+    Hu,
+    /// `ID`: Indonesia
+    Id,
+    /// `IE`: Ireland
+    Ie,
+    /// `IL`: Israel
+    Il,
+    /// `IM`: Isle of Man
+    Im,
+    /// `IN`: India
+    In,
+    /// `IO`: British Indian Ocean Territory
+    Io,
+    /// `IQ`: Iraq
+    Iq,
+    /// `IR`: Iran
+    Ir,
+    /// `IS`: Iceland
+    Is,
+    /// `IT`: Italy
+    It,
+    /// `JE`: Jersey
+    Je,
+    /// `JM`: Jamaica
+    Jm,
+    /// `JO`: Jordan
+    Jo,
+    /// `JP`: Japan
+    Jp,
+    /// `KE`: Kenya
+    Ke,
+    /// `KG`: Kyrgyzstan
+    Kg,
+    /// `KH`: Cambodia
+    Kh,
+    /// `KI`: Kiribati
+    Ki,
+    /// `KM`: Comoros
+    Km,
+    /// `KN`: Saint Kitts and Nevis
+    Kn,
+    /// `KP`: North Korea
+    Kp,
+    /// `KR`: South Korea
+    Kr,
+    /// `KW`: Kuwait
+    Kw,
+    /// `KY`: Cayman Islands
+    Ky,
+    /// `KZ`: Kazakhstan
+    Kz,
+    /// `LA`: Laos
+    La,
+    /// `LB`: Lebanon
+    Lb,
+    /// `LC`: Saint Lucia
+    Lc,
+    /// `LI`: Liechtenstein
+    Li,
+    /// `LK`: Sri Lanka
+    Lk,
+    /// `LR`: Liberia
+    Lr,
+    /// `LS`: Lesotho
+    Ls,
+    /// `LT`: Lithuania
+    Lt,
+    /// `LU`: Luxembourg
+    Lu,
+    /// `LV`: Latvia
+    Lv,
+    /// `LY`: Libya
+    Ly,
+    /// `MA`: Morocco
+    Ma,
+    /// `MC`: Monaco
+    Mc,
+    /// `MD`: Moldova
+    Md,
+    /// `ME`: Montenegro
+    Me,
+    /// `MF`: Saint Martin (French part)
+    Mf,
+    /// `MG`: Madagascar
+    Mg,
+    /// `MH`: Marshall Islands
+    Mh,
+    /// `MK`: North Macedonia
+    Mk,
+    /// `ML`: Mali
+    Ml,
+    /// `MM`: Myanmar
+    Mm,
+    /// `MN`: Mongolia
+    Mn,
+    /// `MO`: Macao
+    Mo,
+    /// `MP`: Northern Mariana Islands
+    Mp,
+    /// `MQ`: Martinique
+    Mq,
+    /// `MR`: Mauritania
+    Mr,
+    /// `MS`: Montserrat
+    Ms,
+    /// `MT`: Malta
+    Mt,
+    /// `MU`: Mauritius
+    Mu,
+    /// `MV`: Maldives
+    Mv,
+    /// `MW`: Malawi
+    Mw,
+    /// `MX`: Mexico
+    Mx,
+    /// `MY`: Malaysia
+    My,
+    /// `MZ`: Mozambique
+    Mz,
+    /// `NA`: Namibia
+    Na,
+    /// `NC`: New Caledonia
+    Nc,
+    /// `NE`: Niger
+    Ne,
+    /// `NF`: Norfolk Island
+    Nf,
+    /// `NG`: Nigeria
+    Ng,
+    /// `NI`: Nicaragua
+    Ni,
+    /// `NL`: Netherlands
+    Nl,
+    /// `NO`: Norway
+    No,
+    /// `NP`: Nepal
+    Np,
+    /// `NR`: Nauru
+    Nr,
+    /// `NU`: Niue
+    Nu,
+    /// `NZ`: New Zealand
+    Nz,
+    /// `OM`: Oman
+    Om,
+    /// `PA`: Panama
+    Pa,
+    /// `PE`: Peru
+    Pe,
+    /// `PF`: French Polynesia
+    Pf,
+    /// `PG`: Papua New Guinea
+    Pg,
+    /// `PH`: Philippines
+    Ph,
+    /// `PK`: Pakistan
+    Pk,
+    /// `PL`: Poland
+    Pl,
+    /// `PM`: Saint Pierre and Miquelon
+    Pm,
+    /// `PN`: Pitcairn
+    Pn,
+    /// `PR`: Puerto Rico
+    Pr,
+    /// `PS`: Palestine, State of
+    Ps,
+    /// `PT`: Portugal
+    Pt,
+    /// `PW`: Palau
+    Pw,
+    /// `PY`: Paraguay
+    Py,
+    /// `QA`: Qatar
+    Qa,
+    /// `RE`: Réunion
+    Re,
+    /// `RO`: Romania
+    Ro,
+    /// `RS`: Serbia
+    Rs,
+    /// `RU`: Russian Federation
+    Ru,
+    /// `RW`: Rwanda
+    Rw,
+    /// `SA`: Saudi Arabia
+    Sa,
+    /// `SB`: Solomon Islands
+    Sb,
+    /// `SC`: Seychelles
+    Sc,
+    /// `SD`: Sudan
+    Sd,
+    /// `SE`: Sweden
+    Se,
+    /// `SG`: Singapore
+    Sg,
+    /// `SH`: Saint Helena, Ascension and Tristan da Cunha
+    Sh,
+    /// `SI`: Slovenia
+    Si,
+    /// `SJ`: Svalbard and Jan Mayen
+    Sj,
+    /// `SK`: Slovakia
+    Sk,
+    /// `SL`: Sierra Leone
+    Sl,
+    /// `SM`: San Marino
+    Sm,
+    /// `SN`: Senegal
+    Sn,
+    /// `SO`: Somalia
+    So,
+    /// `SR`: Suriname
+    Sr,
+    /// `SS`: South Sudan
+    Ss,
+    /// `ST`: Sao Tome and Principe
+    St,
+    /// `SV`: El Salvador
+    Sv,
+    /// `SX`: Sint Maarten (Dutch part)
+    Sx,
+    /// `SY`: Syria
+    Sy,
+    /// `SZ`: Eswatini
+    Sz,
+    /// `TC`: Turks and Caicos Islands
+    Tc,
+    /// `TD`: Chad
+    Td,
+    /// `TF`: French Southern Territories
+    Tf,
+    /// `TG`: Togo
+    Tg,
+    /// `TH`: Thailand
+    Th,
+    /// `TJ`: Tajikistan
+    Tj,
+    /// `TK`: Tokelau
+    Tk,
+    /// `TL`: Timor-Leste
+    Tl,
+    /// `TM`: Turkmenistan
+    Tm,
+    /// `TN`: Tunisia
+    Tn,
+    /// `TO`: Tonga
+    To,
+    /// `TR`: Türkiye
+    Tr,
+    /// `TT`: Trinidad and Tobago
+    Tt,
+    /// `TV`: Tuvalu
+    Tv,
+    /// `TW`: Taiwan
+    Tw,
+    /// `TZ`: Tanzania
+    Tz,
+    /// `UA`: Ukraine
+    Ua,
+    /// `UG`: Uganda
+    Ug,
+    /// `UM`: United States Minor Outlying Islands
+    Um,
     /// `US`: United States of America
-    #[doc(hidden)]
     Us,
+    /// `UY`: Uruguay
+    Uy,
+    /// `UZ`: Uzbekistan
+    Uz,
+    /// `VA`: Holy See (Vatican City State)
+    Va,
+    /// `VC`: Saint Vincent and the Grenadines
+    Vc,
+    /// `VE`: Venezuela
+    Ve,
+    /// `VG`: Virgin Islands, British
+    Vg,
+    /// `VI`: Virgin Islands, U.S.
+    Vi,
+    /// `VN`: Vietnam
+    Vn,
+    /// `VU`: Vanuatu
+    Vu,
+    /// `WF`: Wallis and Futuna
+    Wf,
+    /// `WS`: Samoa
+    Ws,
+    /// `YE`: Yemen
+    Ye,
+    /// `YT`: Mayotte
+    Yt,
+    /// `ZA`: South Africa
+    Za,
+    /// `ZM`: Zambia
+    Zm,
+    /// `ZW`: Zimbabwe
+    Zw,
 }
 
 impl Display for Country {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_str(match self {
             Self::Any => "**",
+            Self::Ad => "AD",
+            Self::Ae => "AE",
+            Self::Af => "AF",
+            Self::Ag => "AG",
+            Self::Ai => "AI",
+            Self::Al => "AL",
+            Self::Am => "AM",
+            Self::Ao => "AO",
+            Self::Aq => "AQ",
+            Self::Ar => "AR",
+            Self::As => "AS",
+            Self::At => "AT",
+            Self::Au => "AU",
+            Self::Aw => "AW",
+            Self::Ax => "AX",
+            Self::Az => "AZ",
+            Self::Ba => "BA",
+            Self::Bb => "BB",
+            Self::Bd => "BD",
+            Self::Be => "BE",
+            Self::Bf => "BF",
+            Self::Bg => "BG",
+            Self::Bh => "BH",
+            Self::Bi => "BI",
+            Self::Bj => "BJ",
+            Self::Bl => "BL",
+            Self::Bm => "BM",
+            Self::Bn => "BN",
+            Self::Bo => "BO",
+            Self::Bq => "BQ",
+            Self::Br => "BR",
+            Self::Bs => "BS",
+            Self::Bt => "BT",
+            Self::Bv => "BV",
+            Self::Bw => "BW",
+            Self::By => "BY",
+            Self::Bz => "BZ",
+            Self::Ca => "CA",
+            Self::Cc => "CC",
+            Self::Cd => "CD",
+            Self::Cf => "CF",
+            Self::Cg => "CG",
+            Self::Ch => "CH",
+            Self::Ci => "CI",
+            Self::Ck => "CK",
+            Self::Cl => "CL",
+            Self::Cm => "CM",
+            Self::Cn => "CN",
+            Self::Co => "CO",
+            Self::Cr => "CR",
+            Self::Cu => "CU",
+            Self::Cv => "CV",
+            Self::Cw => "CW",
+            Self::Cx => "CX",
+            Self::Cy => "CY",
+            Self::Cz => "CZ",
+            Self::De => "DE",
+            Self::Dj => "DJ",
+            Self::Dk => "DK",
+            Self::Dm => "DM",
+            Self::Do => "DO",
+            Self::Dz => "DZ",
+            Self::Ec => "EC",
+            Self::Ee => "EE",
+            Self::Eg => "EG",
+            Self::Eh => "EH",
+            Self::Er => "ER",
+            Self::Es => "ES",
+            Self::Et => "ET",
+            Self::Fi => "FI",
+            Self::Fj => "FJ",
+            Self::Fk => "FK",
+            Self::Fm => "FM",
+            Self::Fo => "FO",
+            Self::Fr => "FR",
+            Self::Ga => "GA",
+            Self::Gb => "GB",
+            Self::Gd => "GD",
+            Self::Ge => "GE",
+            Self::Gf => "GF",
+            Self::Gg => "GG",
+            Self::Gh => "GH",
+            Self::Gi => "GI",
+            Self::Gl => "GL",
+            Self::Gm => "GM",
+            Self::Gn => "GN",
+            Self::Gp => "GP",
+            Self::Gq => "GQ",
+            Self::Gr => "GR",
+            Self::Gs => "GS",
+            Self::Gt => "GT",
+            Self::Gu => "GU",
+            Self::Gw => "GW",
+            Self::Gy => "GY",
+            Self::Hk => "HK",
+            Self::Hm => "HM",
+            Self::Hn => "HN",
+            Self::Hr => "HR",
+            Self::Ht => "HT",
+            Self::Hu => "HU",
+            Self::Id => "ID",
+            Self::Ie => "IE",
+            Self::Il => "IL",
+            Self::Im => "IM",
+            Self::In => "IN",
+            Self::Io => "IO",
+            Self::Iq => "IQ",
+            Self::Ir => "IR",
+            Self::Is => "IS",
+            Self::It => "IT",
+            Self::Je => "JE",
+            Self::Jm => "JM",
+            Self::Jo => "JO",
+            Self::Jp => "JP",
+            Self::Ke => "KE",
+            Self::Kg => "KG",
+            Self::Kh => "KH",
+            Self::Ki => "KI",
+            Self::Km => "KM",
+            Self::Kn => "KN",
+            Self::Kp => "KP",
+            Self::Kr => "KR",
+            Self::Kw => "KW",
+            Self::Ky => "KY",
+            Self::Kz => "KZ",
+            Self::La => "LA",
+            Self::Lb => "LB",
+            Self::Lc => "LC",
+            Self::Li => "LI",
+            Self::Lk => "LK",
+            Self::Lr => "LR",
+            Self::Ls => "LS",
+            Self::Lt => "LT",
+            Self::Lu => "LU",
+            Self::Lv => "LV",
+            Self::Ly => "LY",
+            Self::Ma => "MA",
+            Self::Mc => "MC",
+            Self::Md => "MD",
+            Self::Me => "ME",
+            Self::Mf => "MF",
+            Self::Mg => "MG",
+            Self::Mh => "MH",
+            Self::Mk => "MK",
+            Self::Ml => "ML",
+            Self::Mm => "MM",
+            Self::Mn => "MN",
+            Self::Mo => "MO",
+            Self::Mp => "MP",
+            Self::Mq => "MQ",
+            Self::Mr => "MR",
+            Self::Ms => "MS",
+            Self::Mt => "MT",
+            Self::Mu => "MU",
+            Self::Mv => "MV",
+            Self::Mw => "MW",
+            Self::Mx => "MX",
+            Self::My => "MY",
+            Self::Mz => "MZ",
+            Self::Na => "NA",
+            Self::Nc => "NC",
+            Self::Ne => "NE",
+            Self::Nf => "NF",
+            Self::Ng => "NG",
+            Self::Ni => "NI",
+            Self::Nl => "NL",
+            Self::No => "NO",
+            Self::Np => "NP",
+            Self::Nr => "NR",
+            Self::Nu => "NU",
+            Self::Nz => "NZ",
+            Self::Om => "OM",
+            Self::Pa => "PA",
+            Self::Pe => "PE",
+            Self::Pf => "PF",
+            Self::Pg => "PG",
+            Self::Ph => "PH",
+            Self::Pk => "PK",
+            Self::Pl => "PL",
+            Self::Pm => "PM",
+            Self::Pn => "PN",
+            Self::Pr => "PR",
+            Self::Ps => "PS",
+            Self::Pt => "PT",
+            Self::Pw => "PW",
+            Self::Py => "PY",
+            Self::Qa => "QA",
+            Self::Re => "RE",
+            Self::Ro => "RO",
+            Self::Rs => "RS",
+            Self::Ru => "RU",
+            Self::Rw => "RW",
+            Self::Sa => "SA",
+            Self::Sb => "SB",
+            Self::Sc => "SC",
+            Self::Sd => "SD",
+            Self::Se => "SE",
+            Self::Sg => "SG",
+            Self::Sh => "SH",
+            Self::Si => "SI",
+            Self::Sj => "SJ",
+            Self::Sk => "SK",
+            Self::Sl => "SL",
+            Self::Sm => "SM",
+            Self::Sn => "SN",
+            Self::So => "SO",
+            Self::Sr => "SR",
+            Self::Ss => "SS",
+            Self::St => "ST",
+            Self::Sv => "SV",
+            Self::Sx => "SX",
+            Self::Sy => "SY",
+            Self::Sz => "SZ",
+            Self::Tc => "TC",
+            Self::Td => "TD",
+            Self::Tf => "TF",
+            Self::Tg => "TG",
+            Self::Th => "TH",
+            Self::Tj => "TJ",
+            Self::Tk => "TK",
+            Self::Tl => "TL",
+            Self::Tm => "TM",
+            Self::Tn => "TN",
+            Self::To => "TO",
+            Self::Tr => "TR",
+            Self::Tt => "TT",
+            Self::Tv => "TV",
+            Self::Tw => "TW",
+            Self::Tz => "TZ",
+            Self::Ua => "UA",
+            Self::Ug => "UG",
+            Self::Um => "UM",
             Self::Us => "US",
+            Self::Uy => "UY",
+            Self::Uz => "UZ",
+            Self::Va => "VA",
+            Self::Vc => "VC",
+            Self::Ve => "VE",
+            Self::Vg => "VG",
+            Self::Vi => "VI",
+            Self::Vn => "VN",
+            Self::Vu => "VU",
+            Self::Wf => "WF",
+            Self::Ws => "WS",
+            Self::Ye => "YE",
+            Self::Yt => "YT",
+            Self::Za => "ZA",
+            Self::Zm => "ZM",
+            Self::Zw => "ZW",
+        })
+    }
+}
+
+impl Country {
+    /// Iterate over every country (except [`Country::Any`]), in order of
+    /// country code.
+    pub fn all() -> impl Iterator<Item = Country> {
+        COUNTRIES.iter().copied()
+    }
+
+    /// Get the country from an uppercase ISO 3166-1 alpha-2 region code, such
+    /// as `US`.
+    pub(crate) fn from_region(region: &str) -> Option<Self> {
+        Some(match region {
+            "AD" => Self::Ad,
+            "AE" => Self::Ae,
+            "AF" => Self::Af,
+            "AG" => Self::Ag,
+            "AI" => Self::Ai,
+            "AL" => Self::Al,
+            "AM" => Self::Am,
+            "AO" => Self::Ao,
+            "AQ" => Self::Aq,
+            "AR" => Self::Ar,
+            "AS" => Self::As,
+            "AT" => Self::At,
+            "AU" => Self::Au,
+            "AW" => Self::Aw,
+            "AX" => Self::Ax,
+            "AZ" => Self::Az,
+            "BA" => Self::Ba,
+            "BB" => Self::Bb,
+            "BD" => Self::Bd,
+            "BE" => Self::Be,
+            "BF" => Self::Bf,
+            "BG" => Self::Bg,
+            "BH" => Self::Bh,
+            "BI" => Self::Bi,
+            "BJ" => Self::Bj,
+            "BL" => Self::Bl,
+            "BM" => Self::Bm,
+            "BN" => Self::Bn,
+            "BO" => Self::Bo,
+            "BQ" => Self::Bq,
+            "BR" => Self::Br,
+            "BS" => Self::Bs,
+            "BT" => Self::Bt,
+            "BV" => Self::Bv,
+            "BW" => Self::Bw,
+            "BY" => Self::By,
+            "BZ" => Self::Bz,
+            "CA" => Self::Ca,
+            "CC" => Self::Cc,
+            "CD" => Self::Cd,
+            "CF" => Self::Cf,
+            "CG" => Self::Cg,
+            "CH" => Self::Ch,
+            "CI" => Self::Ci,
+            "CK" => Self::Ck,
+            "CL" => Self::Cl,
+            "CM" => Self::Cm,
+            "CN" => Self::Cn,
+            "CO" => Self::Co,
+            "CR" => Self::Cr,
+            "CU" => Self::Cu,
+            "CV" => Self::Cv,
+            "CW" => Self::Cw,
+            "CX" => Self::Cx,
+            "CY" => Self::Cy,
+            "CZ" => Self::Cz,
+            "DE" => Self::De,
+            "DJ" => Self::Dj,
+            "DK" => Self::Dk,
+            "DM" => Self::Dm,
+            "DO" => Self::Do,
+            "DZ" => Self::Dz,
+            "EC" => Self::Ec,
+            "EE" => Self::Ee,
+            "EG" => Self::Eg,
+            "EH" => Self::Eh,
+            "ER" => Self::Er,
+            "ES" => Self::Es,
+            "ET" => Self::Et,
+            "FI" => Self::Fi,
+            "FJ" => Self::Fj,
+            "FK" => Self::Fk,
+            "FM" => Self::Fm,
+            "FO" => Self::Fo,
+            "FR" => Self::Fr,
+            "GA" => Self::Ga,
+            "GB" => Self::Gb,
+            "GD" => Self::Gd,
+            "GE" => Self::Ge,
+            "GF" => Self::Gf,
+            "GG" => Self::Gg,
+            "GH" => Self::Gh,
+            "GI" => Self::Gi,
+            "GL" => Self::Gl,
+            "GM" => Self::Gm,
+            "GN" => Self::Gn,
+            "GP" => Self::Gp,
+            "GQ" => Self::Gq,
+            "GR" => Self::Gr,
+            "GS" => Self::Gs,
+            "GT" => Self::Gt,
+            "GU" => Self::Gu,
+            "GW" => Self::Gw,
+            "GY" => Self::Gy,
+            "HK" => Self::Hk,
+            "HM" => Self::Hm,
+            "HN" => Self::Hn,
+            "HR" => Self::Hr,
+            "HT" => Self::Ht,
+            "HU" => Self::Hu,
+            "ID" => Self::Id,
+            "IE" => Self::Ie,
+            "IL" => Self::Il,
+            "IM" => Self::Im,
+            "IN" => Self::In,
+            "IO" => Self::Io,
+            "IQ" => Self::Iq,
+            "IR" => Self::Ir,
+            "IS" => Self::Is,
+            "IT" => Self::It,
+            "JE" => Self::Je,
+            "JM" => Self::Jm,
+            "JO" => Self::Jo,
+            "JP" => Self::Jp,
+            "KE" => Self::Ke,
+            "KG" => Self::Kg,
+            "KH" => Self::Kh,
+            "KI" => Self::Ki,
+            "KM" => Self::Km,
+            "KN" => Self::Kn,
+            "KP" => Self::Kp,
+            "KR" => Self::Kr,
+            "KW" => Self::Kw,
+            "KY" => Self::Ky,
+            "KZ" => Self::Kz,
+            "LA" => Self::La,
+            "LB" => Self::Lb,
+            "LC" => Self::Lc,
+            "LI" => Self::Li,
+            "LK" => Self::Lk,
+            "LR" => Self::Lr,
+            "LS" => Self::Ls,
+            "LT" => Self::Lt,
+            "LU" => Self::Lu,
+            "LV" => Self::Lv,
+            "LY" => Self::Ly,
+            "MA" => Self::Ma,
+            "MC" => Self::Mc,
+            "MD" => Self::Md,
+            "ME" => Self::Me,
+            "MF" => Self::Mf,
+            "MG" => Self::Mg,
+            "MH" => Self::Mh,
+            "MK" => Self::Mk,
+            "ML" => Self::Ml,
+            "MM" => Self::Mm,
+            "MN" => Self::Mn,
+            "MO" => Self::Mo,
+            "MP" => Self::Mp,
+            "MQ" => Self::Mq,
+            "MR" => Self::Mr,
+            "MS" => Self::Ms,
+            "MT" => Self::Mt,
+            "MU" => Self::Mu,
+            "MV" => Self::Mv,
+            "MW" => Self::Mw,
+            "MX" => Self::Mx,
+            "MY" => Self::My,
+            "MZ" => Self::Mz,
+            "NA" => Self::Na,
+            "NC" => Self::Nc,
+            "NE" => Self::Ne,
+            "NF" => Self::Nf,
+            "NG" => Self::Ng,
+            "NI" => Self::Ni,
+            "NL" => Self::Nl,
+            "NO" => Self::No,
+            "NP" => Self::Np,
+            "NR" => Self::Nr,
+            "NU" => Self::Nu,
+            "NZ" => Self::Nz,
+            "OM" => Self::Om,
+            "PA" => Self::Pa,
+            "PE" => Self::Pe,
+            "PF" => Self::Pf,
+            "PG" => Self::Pg,
+            "PH" => Self::Ph,
+            "PK" => Self::Pk,
+            "PL" => Self::Pl,
+            "PM" => Self::Pm,
+            "PN" => Self::Pn,
+            "PR" => Self::Pr,
+            "PS" => Self::Ps,
+            "PT" => Self::Pt,
+            "PW" => Self::Pw,
+            "PY" => Self::Py,
+            "QA" => Self::Qa,
+            "RE" => Self::Re,
+            "RO" => Self::Ro,
+            "RS" => Self::Rs,
+            "RU" => Self::Ru,
+            "RW" => Self::Rw,
+            "SA" => Self::Sa,
+            "SB" => Self::Sb,
+            "SC" => Self::Sc,
+            "SD" => Self::Sd,
+            "SE" => Self::Se,
+            "SG" => Self::Sg,
+            "SH" => Self::Sh,
+            "SI" => Self::Si,
+            "SJ" => Self::Sj,
+            "SK" => Self::Sk,
+            "SL" => Self::Sl,
+            "SM" => Self::Sm,
+            "SN" => Self::Sn,
+            "SO" => Self::So,
+            "SR" => Self::Sr,
+            "SS" => Self::Ss,
+            "ST" => Self::St,
+            "SV" => Self::Sv,
+            "SX" => Self::Sx,
+            "SY" => Self::Sy,
+            "SZ" => Self::Sz,
+            "TC" => Self::Tc,
+            "TD" => Self::Td,
+            "TF" => Self::Tf,
+            "TG" => Self::Tg,
+            "TH" => Self::Th,
+            "TJ" => Self::Tj,
+            "TK" => Self::Tk,
+            "TL" => Self::Tl,
+            "TM" => Self::Tm,
+            "TN" => Self::Tn,
+            "TO" => Self::To,
+            "TR" => Self::Tr,
+            "TT" => Self::Tt,
+            "TV" => Self::Tv,
+            "TW" => Self::Tw,
+            "TZ" => Self::Tz,
+            "UA" => Self::Ua,
+            "UG" => Self::Ug,
+            "UM" => Self::Um,
+            "US" => Self::Us,
+            "UY" => Self::Uy,
+            "UZ" => Self::Uz,
+            "VA" => Self::Va,
+            "VC" => Self::Vc,
+            "VE" => Self::Ve,
+            "VG" => Self::Vg,
+            "VI" => Self::Vi,
+            "VN" => Self::Vn,
+            "VU" => Self::Vu,
+            "WF" => Self::Wf,
+            "WS" => Self::Ws,
+            "YE" => Self::Ye,
+            "YT" => Self::Yt,
+            "ZA" => Self::Za,
+            "ZM" => Self::Zm,
+            "ZW" => Self::Zw,
+            _ => return None,
+        })
+    }
+}
+
+/// The system of units of measurement preferred by the user
+#[non_exhaustive]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum MeasurementSystem {
+    /// The metric system (SI units)
+    Metric,
+    /// The imperial system, as used in the United Kingdom
+    Imperial,
+    /// United States customary units
+    Uscs,
+}
+
+impl Display for MeasurementSystem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Metric => "Metric",
+            Self::Imperial => "Imperial",
+            Self::Uscs => "US Customary",
         })
     }
 }
@@ -37,7 +1340,7 @@ impl Display for Country {
 // #[allow(variant_size_differences)]
 pub enum Language {
     #[doc(hidden)]
-    __(Box<String>),
+    __(Box<String>, Option<Country>),
     /// `en`: English
     #[doc(hidden)]
     En(Country),
@@ -47,11 +1350,107 @@ pub enum Language {
 }
 
 impl Language {
+    /// Parse a language tag or locale, such as `en-US` or `sr_RS.UTF-8@latin`.
+    ///
+    /// The encoding is removed, and script modifiers are kept as a script
+    /// subtag (`sr/Latn/RS`).  Returns `None` for the "C" and "POSIX" locales.
+    pub(crate) fn parse(tag: &str) -> Option<Self> {
+        // POSIX locales are `language[_territory][.codeset][@modifier]`
+        let mut parts = tag.splitn(2, '@');
+        let locale = parts.next().unwrap_or_default();
+        let script = parts.next().and_then(|modifier| match modifier {
+            "latin" => Some("Latn"),
+            "cyrillic" => Some("Cyrl"),
+            "devanagari" => Some("Deva"),
+            _ => None,
+        });
+        let locale = locale.split_terminator('.').next().unwrap_or_default();
+
+        if locale == "C" || locale == "POSIX" {
+            return None;
+        }
+
+        let mut lang = locale.replace(|x| ['_', '-'].contains(&x), "/");
+
+        if let Some(script) = script {
+            let end = lang.find('/').unwrap_or(lang.len());
+
+            lang.insert_str(end, &format!("/{}", script));
+        }
+
+        // The region is the first 2 letter subtag after the language
+        let region = lang
+            .split('/')
+            .skip(1)
+            .find(|subtag| {
+                subtag.len() == 2
+                    && subtag.bytes().all(|b| b.is_ascii_alphabetic())
+            })
+            .and_then(|region| {
+                Country::from_region(&region.to_ascii_uppercase())
+            });
+
+        Some(Self::__(Box::new(lang), region))
+    }
+
+    /// Iterate over every ISO 639-1 language, without a region, in order of
+    /// language code.
+    ///
+    /// These compare equal to the languages returned by
+    /// [`langs()`](crate::langs) for the same language tag (such as `en`).
+    pub fn all() -> impl Iterator<Item = Language> {
+        LANGUAGE_CODES.iter().filter_map(|code| Self::parse(code))
+    }
+
+    /// Convert to a POSIX locale name without an encoding, such as `en_US`, for
+    /// passing to C locale APIs like `setlocale()`.
+    ///
+    /// A script subtag is converted back to a modifier (`sr/Latn/RS` becomes
+    /// `sr_RS@latin`), and dropped if POSIX has no modifier for it.
+    pub fn to_posix(&self) -> String {
+        let string = self.to_string();
+        let mut subtags = string.split('/');
+        let mut posix = subtags.next().unwrap_or_default().to_string();
+        let mut modifier = None;
+
+        for subtag in subtags {
+            // Scripts are 4 letters, regions are 2 letters or 3 digits
+            if subtag.len() == 4 {
+                modifier = match subtag {
+                    "Latn" => Some("latin"),
+                    "Cyrl" => Some("cyrillic"),
+                    "Deva" => Some("devanagari"),
+                    _ => None,
+                };
+            } else {
+                posix.push('_');
+                posix.push_str(subtag);
+            }
+        }
+
+        if let Some(modifier) = modifier {
+            posix.push('@');
+            posix.push_str(modifier);
+        }
+
+        posix
+    }
+
     /// Retrieve the country code for this language dialect.
+    ///
+    /// Returns [`Country::Any`] if there's no region, or it isn't a known
+    /// country.
     pub fn country(&self) -> Country {
+        self.region().unwrap_or(Country::Any)
+    }
+
+    /// Retrieve the country from the region subtag of this language dialect
+    /// (`US` in `en/US`), if there is one.
+    pub fn region(&self) -> Option<Country> {
         match self {
-            Self::__(_) => Country::Any,
-            Self::En(country) | Self::Es(country) => *country,
+            Self::__(_, region) => *region,
+            Self::En(Country::Any) | Self::Es(Country::Any) => None,
+            Self::En(country) | Self::Es(country) => Some(*country),
         }
     }
 }
@@ -59,7 +1458,7 @@ impl Language {
 impl Display for Language {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Self::__(code) => f.write_str(code.as_str()),
+            Self::__(code, _) => f.write_str(code.as_str()),
             Self::En(country) => {
                 if *country != Country::Any {
                     f.write_str("en/")?;
@@ -79,3 +1478,33 @@ impl Display for Language {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_script_modifier() {
+        let lang = Language::parse("sr_RS@latin").unwrap();
+
+        assert_eq!(lang.to_string(), "sr/Latn/RS");
+        assert_eq!(lang.region(), Some(Country::Rs));
+        assert_eq!(lang.to_posix(), "sr_RS@latin");
+    }
+
+    #[test]
+    fn parse_strips_encoding() {
+        let tags = ["zh_CN.UTF-8".to_string(), "zh_CN".to_string()];
+        let langs = crate::os::parse_langs(&tags);
+
+        assert_eq!(langs.len(), 1);
+        assert_eq!(langs[0].to_string(), "zh/CN");
+        assert_eq!(langs[0].region(), Some(Country::Cn));
+    }
+
+    #[test]
+    fn parse_posix_locale() {
+        assert_eq!(Language::parse("C"), None);
+        assert_eq!(Language::parse("POSIX.UTF-8"), None);
+    }
+}
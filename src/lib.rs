@@ -77,19 +77,34 @@ mod desktop_env;
 pub mod fallible;
 mod language;
 mod os;
+mod passwd;
 mod platform;
+mod query;
 mod result;
+mod terminal;
+mod validate;
 
 #[allow(deprecated)]
 pub use self::{
     api::{
-        arch, desktop_env, devicename, devicename_os, distro, distro_os,
-        hostname, hostname_os, lang, langs, platform, realname, realname_os,
-        username, username_os,
+        arch, ci_name, color_support, crate_version, desktop_env, devicename,
+        devicename_os, distro, distro_os, endianness, hostname, hostname_os,
+        is_ci, is_sandboxed, is_tty, lang, langs, langs_raw, platform,
+        primary_lang, primary_langs, realname, realname_os, system_langs,
+        target_triple, try_arch, try_desktop_env, try_platform, username,
+        username_lossy, username_os, x86_feature_level,
     },
-    arch::{Arch, Width},
-    desktop_env::DesktopEnv,
-    language::{Country, Language},
-    platform::Platform,
+    arch::{Arch, ArchKind, Endianness, Width},
+    desktop_env::{DesktopEnv, DesktopEnvKind},
+    language::{Country, Language, MeasurementSystem},
+    os::{Os, Target},
+    passwd::Passwd,
+    platform::{LibcKind, PackageFormat, Platform, PlatformKind},
+    query::Query,
     result::Result,
+    terminal::ColorSupport,
+    validate::{
+        is_valid_hostname, sanitize_hostname, HOSTNAME_MAX_LEN,
+        USERNAME_MAX_LEN,
+    },
 };
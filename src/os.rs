@@ -5,6 +5,12 @@
     all(target_arch = "wasm32", target_os = "daku"),
     path = "os/daku.rs"
 )]
+// Emscripten
+#[cfg_attr(target_os = "emscripten", path = "os/emscripten.rs")]
+// Haiku
+#[cfg_attr(target_os = "haiku", path = "os/haiku.rs")]
+// Horizon (Nintendo 3DS and Switch)
+#[cfg_attr(target_os = "horizon", path = "os/horizon.rs")]
 // Redox
 #[cfg_attr(
     all(target_os = "redox", not(target_arch = "wasm32")),
@@ -16,6 +22,7 @@
         any(
             target_os = "linux",
             target_os = "macos",
+            target_os = "ios",
             target_os = "dragonfly",
             target_os = "freebsd",
             target_os = "netbsd",
@@ -37,6 +44,7 @@
         target_arch = "wasm32",
         not(target_os = "wasi"),
         not(target_os = "daku"),
+        not(target_os = "emscripten"),
         feature = "web",
     ),
     path = "os/web.rs"
@@ -48,21 +56,180 @@
 )]
 mod target;
 
+#[cfg(all(target_os = "linux", feature = "dbus"))]
+mod dbus;
+
 use std::{
     env::{self, VarError},
     ffi::OsString,
+    fs,
     io::{Error, ErrorKind},
+    path::Path,
+    time::SystemTime,
+};
+
+use crate::{
+    conversions, Arch, ColorSupport, DesktopEnv, Language, LibcKind,
+    MeasurementSystem, Passwd, Platform, Result,
 };
 
-use crate::{Arch, DesktopEnv, Platform, Result};
+/// The operating system this program is running on.
+///
+/// This is the [`Target`] that all of the functions in this crate query.
+///
+/// ```rust
+/// use whoami::{Os, Target};
+///
+/// println!("{:?}", Os.username());
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Os;
+
+/// A source of information about a system.
+///
+/// [`Os`] implements this for the system this program is running on.  A
+/// custom implementation, such as a mock for testing, or one that queries a
+/// remote machine, can be passed to the `*_with()` functions in [`fallible`].
+///
+/// ```rust
+/// use std::ffi::OsString;
+///
+/// use whoami::{fallible, Arch, DesktopEnv, Language, Platform, Result, Target};
+///
+/// #[derive(Copy, Clone)]
+/// struct Mock;
+///
+/// impl Target for Mock {
+///     fn langs(self) -> Result<Vec<Language>> {
+///         Ok(Language::all().take(1).collect())
+///     }
+///
+///     fn realname(self) -> Result<OsString> {
+///         Ok("Mock User".into())
+///     }
+///
+///     fn username(self) -> Result<OsString> {
+///         Ok("mock".into())
+///     }
+///
+///     fn devicename(self) -> Result<OsString> {
+///         Ok("Mock Device".into())
+///     }
+///
+///     fn hostname(self) -> Result<String> {
+///         Ok("mock-device".to_string())
+///     }
+///
+///     fn distro(self) -> Result<String> {
+///         Ok("Mock OS 1.0".to_string())
+///     }
+///
+///     fn desktop_env(self) -> Result<DesktopEnv> {
+///         Ok(DesktopEnv::Headless)
+///     }
+///
+///     fn platform(self) -> Result<Platform> {
+///         Ok(Platform::Linux)
+///     }
+///
+///     fn arch(self) -> Result<Arch> {
+///         Ok(Arch::X64)
+///     }
+/// }
+///
+/// assert_eq!(fallible::username_with(Mock).unwrap(), "mock");
+/// ```
+pub trait Target: Sized {
+    /// Return the user's preferred languages, most preferred first.
+    fn langs(self) -> Result<Vec<Language>>;
+    /// Return the user's "real" / "full" name.
+    fn realname(self) -> Result<OsString>;
+    /// Return the user's username.
+    fn username(self) -> Result<OsString>;
+    /// Return the computer's "fancy" / "pretty" name.
+    fn devicename(self) -> Result<OsString>;
+    /// Return the computer's hostname.
+    fn hostname(self) -> Result<String>;
+    /// Return the OS distribution's name.
+    fn distro(self) -> Result<String>;
+    /// Return the desktop environment.
+    fn desktop_env(self) -> Result<DesktopEnv>;
+    /// Return the target platform.
+    fn platform(self) -> Result<Platform>;
+    /// Return the computer's CPU architecture.
+    fn arch(self) -> Result<Arch>;
+}
+
+impl Target for Os {
+    #[inline(always)]
+    fn langs(self) -> Result<Vec<Language>> {
+        Backend::langs(self)
+    }
+
+    #[inline(always)]
+    fn realname(self) -> Result<OsString> {
+        Backend::realname(self)
+    }
+
+    #[inline(always)]
+    fn username(self) -> Result<OsString> {
+        Backend::username(self)
+    }
+
+    #[inline(always)]
+    fn devicename(self) -> Result<OsString> {
+        Backend::devicename(self)
+    }
+
+    #[inline(always)]
+    fn hostname(self) -> Result<String> {
+        Backend::hostname(self)
+    }
 
-/// Implement `Target for Os` to add platform support for a target.
-pub(crate) struct Os;
+    #[inline(always)]
+    fn distro(self) -> Result<String> {
+        Backend::distro(self)
+    }
+
+    #[inline(always)]
+    fn desktop_env(self) -> Result<DesktopEnv> {
+        Backend::desktop_env(self)
+    }
+
+    #[inline(always)]
+    fn platform(self) -> Result<Platform> {
+        Backend::platform(self)
+    }
+
+    #[inline(always)]
+    fn arch(self) -> Result<Arch> {
+        Backend::arch(self)
+    }
+}
 
 /// Target platform support
-pub(crate) trait Target: Sized {
-    /// Return a semicolon-delimited string of language/COUNTRY codes.
-    fn langs(self) -> Result<String>;
+///
+/// Implement `Backend for Os` to add platform support for a target.
+pub(crate) trait Backend: Sized {
+    /// Return the user's preferred language tags or locales, as provided by the
+    /// system, most preferred first.
+    fn langs_raw(self) -> Result<Vec<String>>;
+
+    /// Return the user's preferred languages, most preferred first.
+    fn langs(self) -> Result<Vec<Language>> {
+        Ok(parse_langs(&self.langs_raw()?))
+    }
+
+    /// Return the system's preferred language tags, as provided by the system,
+    /// most preferred first.
+    fn system_langs_raw(self) -> Result<Vec<String>> {
+        Err(err_unsupported())
+    }
+
+    /// Return the system's preferred languages, most preferred first.
+    fn system_langs(self) -> Result<Vec<Language>> {
+        Ok(parse_langs(&self.system_langs_raw()?))
+    }
     /// Return the user's "real" / "full" name.
     fn realname(self) -> Result<OsString>;
     /// Return the user's username.
@@ -74,17 +241,210 @@ pub(crate) trait Target: Sized {
     /// Return the OS distribution's name.
     fn distro(self) -> Result<String>;
     /// Return the desktop environment.
-    fn desktop_env(self) -> DesktopEnv;
+    fn desktop_env(self) -> Result<DesktopEnv>;
     /// Return the target platform.
-    fn platform(self) -> Platform;
+    fn platform(self) -> Result<Platform>;
     /// Return the computer's CPU architecture.
     fn arch(self) -> Result<Arch>;
 
+    /// Return the number of logical CPU cores.
+    fn cpu_cores(self) -> Result<usize> {
+        Err(err_unsupported())
+    }
+
+    /// Return the number of physical CPU cores.
+    fn cpu_cores_physical(self) -> Result<usize> {
+        Err(err_unsupported())
+    }
+
+    /// Return the hardware model identifier of the device.
+    fn device_model(self) -> Result<OsString> {
+        Err(err_unsupported())
+    }
+
+    /// Return the total amount of physical memory in bytes.
+    fn memory_total(self) -> Result<u64> {
+        Err(err_unsupported())
+    }
+
+    /// Return the kind of C library used by the operating system.
+    fn libc_kind(self) -> Result<LibcKind> {
+        Err(err_unsupported())
+    }
+
+    /// Return the user's preferred measurement system.
+    fn measurement_system(self) -> Result<MeasurementSystem> {
+        Err(err_unsupported())
+    }
+
+    /// Return the user's preferred decimal separator.
+    fn decimal_separator(self) -> Result<char> {
+        Err(err_unsupported())
+    }
+
+    /// Return the name and major version of the web browser.
+    fn browser(self) -> Result<(String, String)> {
+        Err(err_unsupported())
+    }
+
+    /// Return the hostname configured to be used on boot.
+    fn configured_hostname(self) -> Result<String> {
+        Err(err_unsupported())
+    }
+
+    /// Return the kernel's node name of the host.
+    fn nodename(self) -> Result<String> {
+        Err(err_unsupported())
+    }
+
+    /// Return the fully qualified domain name of the host.
+    fn fqdn(self) -> Result<String> {
+        Err(err_unsupported())
+    }
+
+    /// Return the `(major, minor, build)` version numbers of the OS.
+    fn os_version_raw(self) -> Result<(u32, u32, u32)> {
+        Err(err_unsupported())
+    }
+
+    /// Return the version of the desktop environment.
+    fn desktop_env_version(self) -> Result<String> {
+        Err(err_unsupported())
+    }
+
+    /// Return the architecture of the CPU, even when running under emulation.
+    fn arch_native(self) -> Result<Arch> {
+        self.arch()
+    }
+
+    /// Return the architecture the CPU is capable of running, which may be
+    /// wider than the architecture of the running operating system.
+    fn arch_hardware(self) -> Result<Arch> {
+        self.arch_native()
+    }
+
+    /// Return the time the system was booted.
+    fn boot_time(self) -> Result<SystemTime> {
+        Err(err_unsupported())
+    }
+
+    /// Return true if running inside an application sandbox.
+    fn sandboxed(self) -> bool {
+        false
+    }
+
+    /// Return true if standard output is a terminal.
+    fn tty(self) -> bool {
+        false
+    }
+
+    /// Return the colors supported by the terminal on standard output.
+    fn color_support(self) -> ColorSupport {
+        if self.tty() {
+            color_support_from_env()
+        } else {
+            ColorSupport::None
+        }
+    }
+
+    /// Return the scale factor of the primary display.
+    fn display_scale(self) -> Result<f32> {
+        Err(err_unsupported())
+    }
+
+    /// Return the name of the audio server, or `None` if there isn't one.
+    fn audio_server(self) -> Result<Option<String>> {
+        Err(err_unsupported())
+    }
+
+    /// Return the name of the init system (service manager).
+    fn init_system(self) -> Result<String> {
+        Err(err_unsupported())
+    }
+
+    /// Return the name of the hypervisor, or `None` when running on bare
+    /// metal.
+    fn hypervisor(self) -> Result<Option<String>> {
+        Err(err_unsupported())
+    }
+
+    /// Return the user's whole GECOS field.
+    fn gecos(self) -> Result<OsString> {
+        Err(err_unsupported())
+    }
+
+    /// Return the user's given name and family name, separately.
+    fn realname_given_family(self) -> Result<(OsString, OsString)> {
+        Err(err_unsupported())
+    }
+
+    /// Append the username to `buf`.
+    fn username_into(self, buf: &mut String) -> Result<()> {
+        buf.push_str(&conversions::string_from_os(self.username()?)?);
+
+        Ok(())
+    }
+
+    /// Append the hostname to `buf`.
+    fn hostname_into(self, buf: &mut String) -> Result<()> {
+        buf.push_str(&self.hostname()?);
+
+        Ok(())
+    }
+
+    /// Append the device name to `buf`.
+    fn devicename_into(self, buf: &mut String) -> Result<()> {
+        buf.push_str(&conversions::string_from_os(self.devicename()?)?);
+
+        Ok(())
+    }
+
+    /// Return the full name of the user with the given username.
+    fn realname_of(self, username: &str) -> Result<OsString> {
+        let _ = username;
+
+        Err(err_unsupported())
+    }
+
+    /// Return the home directory of the user with the given username.
+    fn home_dir_of(self, username: &str) -> Result<OsString> {
+        let _ = username;
+
+        Err(err_unsupported())
+    }
+
     /// Return the user's account name (usually just the username, but may
     /// include an account server hostname).
     fn account(self) -> Result<OsString> {
         self.username()
     }
+
+    /// Return the current user's account record.
+    fn passwd(self) -> Result<Passwd> {
+        Err(err_unsupported())
+    }
+
+    /// Return the name of the user's primary group.
+    fn primary_group(self) -> Result<OsString> {
+        Err(err_unsupported())
+    }
+
+    /// Return the OS distribution's machine-readable identifier.
+    fn distro_id(self) -> Result<String> {
+        Err(err_unsupported())
+    }
+
+    /// Return the machine-readable identifiers of the OS distributions this
+    /// one is derived from.
+    fn distro_id_like(self) -> Result<Vec<String>> {
+        Err(err_unsupported())
+    }
+
+    /// Return the OS distribution's release codename, or `None` if it doesn't
+    /// have one.
+    fn distro_codename(self) -> Result<Option<String>> {
+        Err(err_unsupported())
+    }
 }
 
 // This is only used on some platforms
@@ -105,11 +465,235 @@ fn err_empty_record() -> Error {
     Error::new(ErrorKind::NotFound, "Empty record")
 }
 
+fn err_unsupported() -> Error {
+    Error::new(
+        ErrorKind::Other, // FIXME: WhoAmI 2.0, Unsupported
+        "Not supported on this platform",
+    )
+}
+
+/// Read an os-release file, replacing invalid UTF-8.
+fn os_release(path: &Path) -> Result<String> {
+    let os_release = fs::read(path)?;
+
+    Ok(String::from_utf8_lossy(&os_release).into_owned())
+}
+
+/// Look up the (unquoted) value of `field` in the contents of an os-release
+/// file.
+fn os_release_field<'a>(os_release: &'a str, field: &str) -> Option<&'a str> {
+    for line in os_release.lines() {
+        let mut kv = line.splitn(2, '=');
+
+        if kv.next() == Some(field) {
+            return kv
+                .next()
+                .map(|value| value.trim_matches(|c| c == '"' || c == '\''));
+        }
+    }
+
+    None
+}
+
+/// Get the distro name from the contents of an os-release file.
+fn os_release_distro(os_release: &str) -> Result<String> {
+    os_release_field(os_release, "PRETTY_NAME")
+        .or_else(|| os_release_field(os_release, "NAME"))
+        .map(ToString::to_string)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Parsing failed"))
+}
+
+/// Get the distro name from the os-release file at `path`.
+pub(crate) fn distro_from_path(path: &Path) -> Result<String> {
+    os_release_distro(&os_release(path)?)
+}
+
+/// Get the name of the hypervisor from the CPUID hypervisor leaf, or `None`
+/// if the CPU doesn't report running under a hypervisor.
 // This is only used on some platforms
 #[allow(dead_code)]
-fn unix_lang() -> Result<String> {
-    let check_var = |var| {
-        env::var(var).map_err(|e| {
+// `__cpuid()` is only `unsafe` on older versions of Rust
+#[allow(unused_unsafe)]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn cpuid_hypervisor() -> Option<String> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::__cpuid;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::__cpuid;
+
+    // Bit 31 of ECX in leaf 1 is reserved for hypervisors to set
+    if unsafe { __cpuid(1) }.ecx & (1 << 31) == 0 {
+        return None;
+    }
+
+    let leaf = unsafe { __cpuid(0x4000_0000) };
+    let mut vendor = Vec::with_capacity(12);
+
+    for register in &[leaf.ebx, leaf.ecx, leaf.edx] {
+        vendor.extend_from_slice(&register.to_le_bytes());
+    }
+
+    let vendor = String::from_utf8_lossy(&vendor);
+    let name = match vendor.trim_end_matches('\0') {
+        "KVMKVMKVM" => "KVM",
+        "Microsoft Hv" => "Hyper-V",
+        "VMwareVMware" => "VMware",
+        "XenVMMXenVMM" => "Xen",
+        "VBoxVBoxVBox" => "VirtualBox",
+        "TCGTCGTCGTCG" => "QEMU",
+        "bhyve bhyve " => "bhyve",
+        "ACRNACRNACRN" => "ACRN",
+        " lrpepyh  vr" | "prl hyperv  " => "Parallels",
+        "" => "Unknown",
+        other => other.trim(),
+    };
+
+    Some(name.to_string())
+}
+
+/// Get the x86-64 microarchitecture level (1 to 4) supported by the CPU and
+/// operating system, from the CPUID feature flags.
+// `__cpuid()` and `__cpuid_count()` are only `unsafe` on older versions of Rust
+#[allow(unused_unsafe)]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(crate) fn x86_feature_level() -> Option<u8> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::{__cpuid, __cpuid_count, _xgetbv};
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::{__cpuid, __cpuid_count, _xgetbv};
+
+    let has = |register: u32, bits: &[u32]| {
+        bits.iter().all(|bit| register & (1 << bit) != 0)
+    };
+    let max_leaf = unsafe { __cpuid(0) }.eax;
+    let max_extended_leaf = unsafe { __cpuid(0x8000_0000) }.eax;
+
+    if max_leaf < 1 || max_extended_leaf < 0x8000_0001 {
+        return None;
+    }
+
+    let leaf1 = unsafe { __cpuid(1) };
+    let extended = unsafe { __cpuid(0x8000_0001) };
+    let leaf7_ebx = if max_leaf >= 7 {
+        unsafe { __cpuid_count(7, 0) }.ebx
+    } else {
+        0
+    };
+    // Bits of XCR0 for the register state saved by the operating system
+    let xcr0 = if has(leaf1.ecx, &[27 /* OSXSAVE */]) {
+        unsafe { _xgetbv(0) }
+    } else {
+        0
+    };
+    let xcr0_has = |bits: &[u32]| bits.iter().all(|bit| xcr0 & (1 << bit) != 0);
+
+    // x86-64 baseline: FPU, CX8, CMOV, MMX, FXSR, SSE, SSE2, SYSCALL, and LM
+    if !has(leaf1.edx, &[0, 8, 15, 23, 24, 25, 26])
+        || !has(extended.edx, &[11, 29])
+    {
+        return None;
+    }
+
+    // x86-64-v2: SSE3, SSSE3, CMPXCHG16B, SSE4.1, SSE4.2, POPCNT, and
+    // LAHF/SAHF
+    if !has(leaf1.ecx, &[0, 9, 13, 19, 20, 23]) || !has(extended.ecx, &[0]) {
+        return Some(1);
+    }
+
+    // x86-64-v3: FMA, MOVBE, AVX, F16C, BMI1, AVX2, BMI2, LZCNT, and the
+    // operating system saving the SSE and AVX state
+    if !has(leaf1.ecx, &[12, 22, 28, 29])
+        || !has(leaf7_ebx, &[3, 5, 8])
+        || !has(extended.ecx, &[5])
+        || !xcr0_has(&[1, 2])
+    {
+        return Some(2);
+    }
+
+    // x86-64-v4: AVX512F, AVX512DQ, AVX512CD, AVX512BW, AVX512VL, and the
+    // operating system saving the AVX-512 state
+    if !has(leaf7_ebx, &[16, 17, 28, 30, 31]) || !xcr0_has(&[5, 6, 7]) {
+        return Some(3);
+    }
+
+    Some(4)
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub(crate) fn x86_feature_level() -> Option<u8> {
+    None
+}
+
+/// Detect the colors supported by the terminal from the `NO_COLOR`,
+/// `COLORTERM` and `TERM` environment variables.
+fn color_support_from_env() -> ColorSupport {
+    if env::var_os("NO_COLOR").map_or(false, |v| !v.is_empty()) {
+        return ColorSupport::None;
+    }
+
+    let colorterm = env::var("COLORTERM").unwrap_or_default();
+
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorSupport::TrueColor;
+    }
+
+    // Example: "xterm-256color", or "xterm-direct" for 24-bit color
+    let term = env::var("TERM").unwrap_or_default();
+
+    if term.is_empty() || term == "dumb" {
+        ColorSupport::None
+    } else if term.ends_with("-direct") {
+        ColorSupport::TrueColor
+    } else if term.contains("256color") {
+        ColorSupport::Ansi256
+    } else {
+        ColorSupport::Ansi16
+    }
+}
+
+/// Get the language tags for targets without a system to query, set at build
+/// time with the comma-separated `WHOAMI_FAKE_LANGS` environment variable
+/// (defaults to "en-US").
+pub(crate) fn fake_langs() -> Vec<String> {
+    option_env!("WHOAMI_FAKE_LANGS")
+        .unwrap_or("en-US")
+        .split(',')
+        .map(str::trim)
+        .filter(|lang| !lang.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Parse language tags or locales, skipping those that don't name a language
+/// (such as the "C" locale), and duplicates (such as locales that only differ
+/// by encoding).
+pub(crate) fn parse_langs(tags: &[String]) -> Vec<Language> {
+    let mut langs: Vec<Language> = Vec::new();
+
+    for lang in tags.iter().filter_map(|tag| Language::parse(tag)) {
+        if !langs.contains(&lang) {
+            langs.push(lang);
+        }
+    }
+
+    langs
+}
+
+// This is only used on some platforms
+#[allow(dead_code)]
+fn unix_lang() -> Result<Vec<String>> {
+    unix_lang_from(|var| env::var(var))
+}
+
+/// Get the language preferences from the environment variables, as returned
+/// by `var`.
+// This is only used on some platforms
+#[allow(dead_code)]
+fn unix_lang_from(
+    var: impl Fn(&str) -> std::result::Result<String, VarError>,
+) -> Result<Vec<String>> {
+    let check_var = |name: &str| {
+        var(name).map_err(|e| {
             let kind = match e {
                 VarError::NotPresent => ErrorKind::NotFound,
                 VarError::NotUnicode(_) => ErrorKind::InvalidData,
@@ -117,11 +701,156 @@ fn unix_lang() -> Result<String> {
             Error::new(kind, e)
         })
     };
-    let langs = check_var("LANGS").or_else(|_| check_var("LANG"))?;
+
+    let mut error = None;
+    let mut lookup = |name: &str| match check_var(name) {
+        Ok(value) if !value.is_empty() => Some(value),
+        Ok(_) => {
+            if error
+                .as_ref()
+                .map_or(true, |e: &Error| e.kind() == ErrorKind::NotFound)
+            {
+                error = Some(err_empty_record());
+            }
+            None
+        }
+        Err(e) => {
+            if error.is_none() || e.kind() != ErrorKind::NotFound {
+                error = Some(e);
+            }
+            None
+        }
+    };
+    let split = |list: String, separator: char| -> Vec<String> {
+        list.split(separator)
+            .filter(|lang| !lang.is_empty())
+            .map(ToString::to_string)
+            .collect()
+    };
+
+    // `LC_ALL` overrides everything, including `LANGUAGE`
+    let langs = if let Some(locale) = lookup("LC_ALL") {
+        vec![locale]
+    } else if let Some(locale) =
+        ["LC_MESSAGES", "LANG"].iter().find_map(|name| lookup(name))
+    {
+        let name = locale.split('.').next().unwrap_or_default();
+        // `LANGUAGE` is a colon-separated list of preferences, which (as with
+        // GNU gettext) is ignored for the "C" locale
+        let language = if name == "C" || name == "POSIX" {
+            None
+        } else {
+            lookup("LANGUAGE")
+        };
+
+        language
+            .map(|language| split(language, ':'))
+            .filter(|langs| !langs.is_empty())
+            .unwrap_or_else(|| vec![locale])
+    } else {
+        // Semicolon-separated list, only used when no locale is set
+        lookup("LANGS")
+            .map(|langs| split(langs, ';'))
+            .unwrap_or_default()
+    };
 
     if langs.is_empty() {
-        return Err(err_empty_record());
+        return Err(error.unwrap_or_else(err_empty_record));
     }
 
     Ok(langs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(
+        vars: &[(&str, &str)],
+    ) -> impl Fn(&str) -> std::result::Result<String, VarError> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|&(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        move |name| {
+            vars.iter()
+                .find(|(var, _)| var == name)
+                .map(|(_, value)| value.clone())
+                .ok_or(VarError::NotPresent)
+        }
+    }
+
+    fn langs(vars: &[(&str, &str)]) -> Vec<String> {
+        parse_langs(&unix_lang_from(self::vars(vars)).unwrap())
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn unix_lang_lc_all() {
+        assert_eq!(
+            langs(&[
+                ("LC_ALL", "de_DE.UTF-8"),
+                ("LANGUAGE", "fr:en"),
+                ("LC_MESSAGES", "es_ES.UTF-8"),
+                ("LANG", "en_US.UTF-8"),
+            ]),
+            ["de/DE"],
+        );
+        assert_eq!(
+            langs(&[("LC_ALL", ""), ("LANG", "en_US.UTF-8")]),
+            ["en/US"],
+        );
+    }
+
+    #[test]
+    fn unix_lang_language() {
+        assert_eq!(
+            langs(&[
+                ("LANGUAGE", "fr_FR:en"),
+                ("LC_MESSAGES", "es_ES.UTF-8"),
+                ("LANG", "de_DE.UTF-8"),
+            ]),
+            ["fr/FR", "en"],
+        );
+        assert_eq!(
+            langs(&[("LANGUAGE", "fr_FR:en"), ("LANG", "de_DE.UTF-8")]),
+            ["fr/FR", "en"],
+        );
+        assert_eq!(
+            langs(&[("LC_MESSAGES", "es_ES.UTF-8"), ("LANG", "de_DE.UTF-8")]),
+            ["es/ES"],
+        );
+    }
+
+    #[test]
+    fn unix_lang_posix_locale() {
+        for &locale in ["C", "C.UTF-8", "POSIX"].iter() {
+            assert!(
+                langs(&[("LANGUAGE", "fr_FR:en"), ("LANG", locale)]).is_empty()
+            );
+        }
+    }
+
+    #[test]
+    fn unix_lang_dedupe() {
+        assert_eq!(
+            langs(&[
+                ("LANGUAGE", "en_US.UTF-8:en_US:en:en_US"),
+                ("LANG", "en_US.UTF-8"),
+            ]),
+            ["en/US", "en"],
+        );
+    }
+
+    #[test]
+    fn unix_lang_langs() {
+        assert_eq!(langs(&[("LANGS", "fr_FR;en")]), ["fr/FR", "en"]);
+        assert_eq!(
+            unix_lang_from(vars(&[])).unwrap_err().kind(),
+            ErrorKind::NotFound,
+        );
+    }
+}
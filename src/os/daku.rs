@@ -1,19 +1,16 @@
-//! This is mostly the same as fake.rs for now
+//! This is mostly the same as the fake implementation in target.rs for now
 
-use std::{
-    ffi::OsString,
-    io::{Error, ErrorKind},
-};
+use std::ffi::OsString;
 
 use crate::{
-    os::{Os, Target},
+    os::{Backend, Os},
     Arch, DesktopEnv, Platform, Result,
 };
 
-impl Target for Os {
+impl Backend for Os {
     #[inline(always)]
-    fn langs(self) -> Result<String> {
-        Ok("en/US".to_string())
+    fn langs_raw(self) -> Result<Vec<String>> {
+        Ok(super::fake_langs())
     }
 
     #[inline(always)]
@@ -42,26 +39,17 @@ impl Target for Os {
     }
 
     #[inline(always)]
-    fn desktop_env(self) -> DesktopEnv {
-        DesktopEnv::Unknown("Unknown Daku".to_string())
+    fn desktop_env(self) -> Result<DesktopEnv> {
+        Ok(DesktopEnv::Unknown("Unknown Daku".to_string()))
     }
 
     #[inline(always)]
-    fn platform(self) -> Platform {
-        Platform::Unknown("Daku".to_string())
+    fn platform(self) -> Result<Platform> {
+        Ok(Platform::current())
     }
 
     #[inline(always)]
     fn arch(self) -> Result<Arch> {
-        Ok(if cfg!(target_pointer_width = "64") {
-            Arch::Wasm64
-        } else if cfg!(target_pointer_width = "32") {
-            Arch::Wasm32
-        } else {
-            return Err(Error::new(
-                ErrorKind::Other, // FIXME: WhoAmI 2.0, Unsupported
-                "Unexpected pointer width for target platform",
-            ));
-        })
+        Ok(Arch::current())
     }
 }
@@ -0,0 +1,356 @@
+//! Minimal D-Bus client, for reading properties on the system bus without
+//! linking to libdbus.
+
+use std::{
+    env,
+    io::{Error, ErrorKind, Read, Write},
+    os::unix::net::UnixStream,
+    time::Duration,
+};
+
+use crate::Result;
+
+/// Default address of the system bus socket
+const SYSTEM_BUS: &str = "/var/run/dbus/system_bus_socket";
+/// Maximum length of a message, from the D-Bus specification
+const MAX_MESSAGE_LEN: usize = 1 << 27;
+
+const METHOD_CALL: u8 = 1;
+const METHOD_RETURN: u8 = 2;
+const ERROR: u8 = 3;
+
+const FIELD_PATH: u8 = 1;
+const FIELD_INTERFACE: u8 = 2;
+const FIELD_MEMBER: u8 = 3;
+const FIELD_REPLY_SERIAL: u8 = 5;
+const FIELD_DESTINATION: u8 = 6;
+const FIELD_SIGNATURE: u8 = 8;
+
+extern "system" {
+    fn geteuid() -> u32;
+}
+
+fn err_protocol() -> Error {
+    Error::new(ErrorKind::InvalidData, "D-Bus protocol error")
+}
+
+/// Little-endian message writer
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn align(&mut self, alignment: usize) {
+        while self.0.len() % alignment != 0 {
+            self.0.push(0);
+        }
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.align(4);
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn string(&mut self, string: &str) {
+        self.u32(string.len() as u32);
+        self.0.extend_from_slice(string.as_bytes());
+        self.0.push(0);
+    }
+
+    fn signature(&mut self, signature: &str) {
+        self.0.push(signature.len() as u8);
+        self.0.extend_from_slice(signature.as_bytes());
+        self.0.push(0);
+    }
+
+    fn field(&mut self, code: u8, signature: &str, value: &str) {
+        self.align(8);
+        self.0.push(code);
+        self.signature(signature);
+
+        if signature == "g" {
+            self.signature(value);
+        } else {
+            self.string(value);
+        }
+    }
+}
+
+/// Message reader, for either byte order
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn align(&mut self, alignment: usize) {
+        self.pos += (alignment - self.pos % alignment) % alignment;
+    }
+
+    fn byte(&mut self) -> Option<u8> {
+        let byte = *self.data.get(self.pos)?;
+
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.align(4);
+
+        let bytes = self.bytes(4)?;
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+
+        Some(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    fn string(&mut self) -> Option<&'a [u8]> {
+        let len = self.u32()? as usize;
+        let string = self.bytes(len)?;
+
+        self.pos += 1; // NUL
+        Some(string)
+    }
+
+    fn signature(&mut self) -> Option<&'a [u8]> {
+        let len = self.byte()?.into();
+        let signature = self.bytes(len)?;
+
+        self.pos += 1; // NUL
+        Some(signature)
+    }
+}
+
+struct Reply {
+    kind: u8,
+    reply_serial: Option<u32>,
+    big_endian: bool,
+    body: Vec<u8>,
+}
+
+fn method_call(
+    serial: u32,
+    destination: &str,
+    path: &str,
+    interface: &str,
+    member: &str,
+    args: &[&str],
+) -> Vec<u8> {
+    let mut body = Writer(Vec::new());
+
+    for arg in args {
+        body.string(arg);
+    }
+
+    let mut message = Writer(vec![b'l', METHOD_CALL, 0, 1]);
+
+    message.u32(body.0.len() as u32);
+    message.u32(serial);
+    message.u32(0); // Length of the header fields, filled in below
+    message.field(FIELD_PATH, "o", path);
+    message.field(FIELD_INTERFACE, "s", interface);
+    message.field(FIELD_MEMBER, "s", member);
+    message.field(FIELD_DESTINATION, "s", destination);
+
+    if !args.is_empty() {
+        message.field(FIELD_SIGNATURE, "g", &"s".repeat(args.len()));
+    }
+
+    let fields_len = (message.0.len() - 16) as u32;
+
+    message.0[12..16].copy_from_slice(&fields_len.to_le_bytes());
+    message.align(8);
+    message.0.extend_from_slice(&body.0);
+    message.0
+}
+
+fn read_message(stream: &mut UnixStream) -> Result<Reply> {
+    let mut message = vec![0; 16];
+
+    stream.read_exact(&mut message)?;
+
+    let big_endian = match message[0] {
+        b'l' => false,
+        b'B' => true,
+        _ => return Err(err_protocol()),
+    };
+    let kind = message[1];
+    let mut reader = Reader {
+        data: &message,
+        pos: 4,
+        big_endian,
+    };
+    let body_len = reader.u32().ok_or_else(err_protocol)? as usize;
+    let _serial = reader.u32().ok_or_else(err_protocol)?;
+    let fields_len = reader.u32().ok_or_else(err_protocol)? as usize;
+    let fields_end = 16 + fields_len;
+    let body_start = fields_end + (8 - fields_end % 8) % 8;
+
+    if body_start + body_len > MAX_MESSAGE_LEN {
+        return Err(err_protocol());
+    }
+
+    message.resize(body_start + body_len, 0);
+    stream.read_exact(&mut message[16..])?;
+
+    let mut reader = Reader {
+        data: &message[..fields_end],
+        pos: 16,
+        big_endian,
+    };
+    let mut reply_serial = None;
+
+    while reader.pos < fields_end {
+        reader.align(8);
+
+        let code = reader.byte().ok_or_else(err_protocol)?;
+        let signature = reader.signature().ok_or_else(err_protocol)?;
+
+        match signature {
+            b"u" => {
+                let value = reader.u32().ok_or_else(err_protocol)?;
+
+                if code == FIELD_REPLY_SERIAL {
+                    reply_serial = Some(value);
+                }
+            }
+            b"s" | b"o" => {
+                reader.string().ok_or_else(err_protocol)?;
+            }
+            b"g" => {
+                reader.signature().ok_or_else(err_protocol)?;
+            }
+            _ => return Err(err_protocol()),
+        }
+    }
+
+    Ok(Reply {
+        kind,
+        reply_serial,
+        big_endian,
+        body: message.split_off(body_start),
+    })
+}
+
+/// Connect to the system bus, and authenticate as the current user.
+fn connect() -> Result<UnixStream> {
+    let address = env::var("DBUS_SYSTEM_BUS_ADDRESS").ok();
+    // Example: "unix:path=/run/dbus/system_bus_socket"
+    let path = address
+        .as_ref()
+        .and_then(|address| {
+            let address = address.split(';').next()?;
+
+            if address.starts_with("unix:path=") {
+                address["unix:path=".len()..].split(',').next()
+            } else {
+                None
+            }
+        })
+        .unwrap_or(SYSTEM_BUS);
+    let mut stream = UnixStream::connect(path)?;
+    let timeout = Some(Duration::from_secs(1));
+
+    stream.set_read_timeout(timeout)?;
+    stream.set_write_timeout(timeout)?;
+
+    // The EXTERNAL mechanism takes the user ID as hex-encoded ASCII digits
+    let uid = unsafe { geteuid() }
+        .to_string()
+        .bytes()
+        .map(|digit| format!("{:02x}", digit))
+        .collect::<String>();
+
+    stream.write_all(format!("\0AUTH EXTERNAL {}\r\n", uid).as_bytes())?;
+
+    let mut line = Vec::new();
+    let mut byte = [0];
+
+    while !line.ends_with(b"\r\n") {
+        stream.read_exact(&mut byte)?;
+        line.push(byte[0]);
+    }
+
+    if !line.starts_with(b"OK ") {
+        return Err(Error::new(
+            ErrorKind::PermissionDenied,
+            "D-Bus authentication failed",
+        ));
+    }
+
+    stream.write_all(b"BEGIN\r\n")?;
+
+    Ok(stream)
+}
+
+/// Get a string property of an object on the system bus.
+pub(super) fn system_property(
+    destination: &str,
+    path: &str,
+    interface: &str,
+    property: &str,
+) -> Result<String> {
+    const GET_SERIAL: u32 = 2;
+
+    let mut stream = connect()?;
+
+    stream.write_all(&method_call(
+        1,
+        "org.freedesktop.DBus",
+        "/org/freedesktop/DBus",
+        "org.freedesktop.DBus",
+        "Hello",
+        &[],
+    ))?;
+    stream.write_all(&method_call(
+        GET_SERIAL,
+        destination,
+        path,
+        "org.freedesktop.DBus.Properties",
+        "Get",
+        &[interface, property],
+    ))?;
+
+    // Skip the reply to `Hello` and any signals
+    let reply = loop {
+        let reply = read_message(&mut stream)?;
+
+        if reply.reply_serial == Some(GET_SERIAL)
+            && (reply.kind == METHOD_RETURN || reply.kind == ERROR)
+        {
+            break reply;
+        }
+    };
+
+    if reply.kind == ERROR {
+        return Err(Error::new(ErrorKind::Other, "D-Bus error reply"));
+    }
+
+    // The property value is a variant, which must contain a string
+    let mut reader = Reader {
+        data: &reply.body,
+        pos: 0,
+        big_endian: reply.big_endian,
+    };
+
+    if reader.signature() != Some(&b"s"[..]) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Property not a string",
+        ));
+    }
+
+    let value = reader.string().ok_or_else(err_protocol)?;
+
+    String::from_utf8(value.to_vec())
+        .map_err(|_| Error::new(ErrorKind::InvalidData, "Property not UTF-8"))
+}
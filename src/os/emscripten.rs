@@ -0,0 +1,125 @@
+//! Emscripten target.
+//!
+//! Emscripten runs in either a browser or Node.js, so the host platform is
+//! queried through JavaScript, and everything else through Emscripten's
+//! emulated environment.
+
+use std::{
+    env,
+    ffi::{CStr, OsString},
+    os::raw::c_char,
+};
+
+use crate::{
+    os::{Backend, Os},
+    Arch, DesktopEnv, Platform, Result,
+};
+
+extern "C" {
+    fn emscripten_run_script_string(script: *const c_char) -> *const c_char;
+}
+
+/// Whether running in Node.js rather than a browser
+const IS_NODE: &[u8] = b"typeof process === 'object' \
+    && typeof process.versions === 'object' \
+    && process.versions.node ? '1' : ''\0";
+/// Node.js `process.platform`, or browser `navigator.platform`
+const HOST_PLATFORM: &[u8] = b"typeof process === 'object' \
+    && process.platform ? process.platform \
+    : typeof navigator === 'object' ? navigator.platform : ''\0";
+
+/// Run a JavaScript expression, returning its value as a string.
+fn run_script(script: &[u8]) -> Option<String> {
+    // The returned string is owned by Emscripten, and valid until the next
+    // call
+    let string =
+        unsafe { emscripten_run_script_string(script.as_ptr().cast()) };
+
+    if string.is_null() {
+        return None;
+    }
+
+    let string = unsafe { CStr::from_ptr(string) }.to_string_lossy();
+
+    if string.is_empty() || string == "undefined" || string == "null" {
+        return None;
+    }
+
+    Some(string.into_owned())
+}
+
+fn is_node() -> bool {
+    run_script(IS_NODE).is_some()
+}
+
+impl Backend for Os {
+    fn langs_raw(self) -> Result<Vec<String>> {
+        super::unix_lang()
+    }
+
+    #[inline(always)]
+    fn realname(self) -> Result<OsString> {
+        Ok("Anonymous".to_string().into())
+    }
+
+    fn username(self) -> Result<OsString> {
+        // Emscripten sets `$USER` to "web_user" by default
+        Ok(env::var_os("USER").unwrap_or_else(|| "anonymous".into()))
+    }
+
+    fn devicename(self) -> Result<OsString> {
+        Ok(if is_node() { "Node.js" } else { "Web Browser" }
+            .to_string()
+            .into())
+    }
+
+    #[inline(always)]
+    fn hostname(self) -> Result<String> {
+        Ok("localhost".to_string())
+    }
+
+    fn distro(self) -> Result<String> {
+        Ok(format!("Emscripten ({})", self.platform()?))
+    }
+
+    fn desktop_env(self) -> Result<DesktopEnv> {
+        Ok(if is_node() {
+            DesktopEnv::Unknown("Node.js".to_string())
+        } else {
+            DesktopEnv::WebBrowser
+        })
+    }
+
+    fn platform(self) -> Result<Platform> {
+        let platform = if let Some(platform) = run_script(HOST_PLATFORM) {
+            platform
+        } else {
+            return Ok(Platform::current());
+        };
+        // Examples: "linux", "darwin", "win32", "Linux x86_64", "MacIntel"
+        let lower = platform.to_ascii_lowercase();
+
+        Ok(if lower.starts_with("android") {
+            Platform::Android
+        } else if lower.starts_with("linux") {
+            Platform::Linux
+        } else if lower.starts_with("darwin") || lower.starts_with("mac") {
+            Platform::MacOS
+        } else if lower.starts_with("win") {
+            Platform::Windows
+        } else if lower.starts_with("iphone") || lower.starts_with("ipad") {
+            Platform::Ios
+        } else if lower.ends_with("bsd") || lower.starts_with("dragonfly") {
+            Platform::Bsd
+        } else if lower.starts_with("sunos") {
+            Platform::Illumos
+        } else {
+            Platform::Unknown(platform)
+        })
+    }
+
+    #[inline(always)]
+    fn arch(self) -> Result<Arch> {
+        Ok(Arch::current())
+    }
+}
@@ -0,0 +1,202 @@
+//! Haiku target.
+
+use std::{
+    ffi::{c_void, CStr, OsString},
+    io::{Error, ErrorKind},
+    mem,
+    os::{
+        raw::{c_char, c_int},
+        unix::ffi::OsStringExt,
+    },
+};
+
+use crate::{
+    os::{Backend, Os},
+    Arch, DesktopEnv, Platform, Result, HOSTNAME_MAX_LEN,
+};
+
+#[repr(C)]
+struct PassWd {
+    pw_name: *const c_char,
+    pw_passwd: *const c_char,
+    pw_uid: u32,
+    pw_gid: u32,
+    pw_dir: *const c_char,
+    pw_shell: *const c_char,
+    pw_gecos: *const c_char,
+}
+
+#[repr(C)]
+struct UtsName {
+    sysname: [c_char; 32],
+    nodename: [c_char; 32],
+    release: [c_char; 32],
+    version: [c_char; 32],
+    machine: [c_char; 32],
+}
+
+extern "C" {
+    fn geteuid() -> u32;
+    fn getpwuid_r(
+        uid: u32,
+        pwd: *mut PassWd,
+        buf: *mut c_void,
+        buflen: usize,
+        result: *mut *mut PassWd,
+    ) -> c_int;
+    fn gethostname(name: *mut c_char, len: usize) -> c_int;
+    fn uname(buf: *mut UtsName) -> c_int;
+}
+
+enum Name {
+    User,
+    Real,
+    Gecos,
+}
+
+// This function must allocate, because a slice or `Cow<OsStr>` would still
+// reference `passwd` which is dropped when this function returns.
+fn getpwuid(name: Name) -> Result<OsString> {
+    const BUF_SIZE: usize = 16_384; // size from the man page
+    let mut buffer = mem::MaybeUninit::<[u8; BUF_SIZE]>::uninit();
+    let mut passwd = mem::MaybeUninit::<PassWd>::uninit();
+    let mut result = mem::MaybeUninit::<*mut PassWd>::uninit();
+
+    // Get PassWd `struct`.
+    let passwd = unsafe {
+        let ret = getpwuid_r(
+            geteuid(),
+            passwd.as_mut_ptr(),
+            buffer.as_mut_ptr().cast(),
+            BUF_SIZE,
+            result.as_mut_ptr(),
+        );
+
+        if ret != 0 {
+            return Err(Error::from_raw_os_error(ret));
+        }
+
+        if result.assume_init().is_null() {
+            return Err(super::err_null_record());
+        }
+
+        passwd.assume_init()
+    };
+
+    // Extract names.
+    let string = match name {
+        Name::User => passwd.pw_name,
+        Name::Real | Name::Gecos => passwd.pw_gecos,
+    };
+
+    if string.is_null() {
+        return Err(super::err_null_record());
+    }
+
+    let mut bytes = unsafe { CStr::from_ptr(string) }.to_bytes();
+
+    // Only the first field of GECOS is the full name
+    if let Name::Real = name {
+        bytes = bytes.split(|b| *b == b',').next().unwrap_or_default();
+    }
+
+    if bytes.is_empty() {
+        return Err(super::err_empty_record());
+    }
+
+    Ok(OsString::from_vec(bytes.to_vec()))
+}
+
+fn utsname() -> Result<UtsName> {
+    let mut buf = mem::MaybeUninit::<UtsName>::zeroed();
+
+    if unsafe { uname(buf.as_mut_ptr()) } == -1 {
+        return Err(Error::last_os_error());
+    }
+
+    Ok(unsafe { buf.assume_init() })
+}
+
+fn field(field: &[c_char]) -> String {
+    unsafe { CStr::from_ptr(field.as_ptr()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+impl Backend for Os {
+    fn langs_raw(self) -> Result<Vec<String>> {
+        super::unix_lang()
+    }
+
+    fn realname(self) -> Result<OsString> {
+        getpwuid(Name::Real)
+    }
+
+    fn username(self) -> Result<OsString> {
+        getpwuid(Name::User)
+    }
+
+    fn gecos(self) -> Result<OsString> {
+        getpwuid(Name::Gecos)
+    }
+
+    fn devicename(self) -> Result<OsString> {
+        self.hostname().map(OsString::from)
+    }
+
+    fn hostname(self) -> Result<String> {
+        // Maximum hostname length, plus a NULL byte.
+        let mut string: [c_char; HOSTNAME_MAX_LEN + 1] =
+            [0; HOSTNAME_MAX_LEN + 1];
+
+        if unsafe { gethostname(string.as_mut_ptr(), HOSTNAME_MAX_LEN) } == -1 {
+            return Err(Error::last_os_error());
+        }
+
+        unsafe { CStr::from_ptr(string.as_ptr()) }
+            .to_str()
+            .map(ToString::to_string)
+            .map_err(|_| {
+                Error::new(ErrorKind::InvalidData, "Hostname not valid UTF-8")
+            })
+    }
+
+    fn nodename(self) -> Result<String> {
+        let nodename = field(&utsname()?.nodename);
+
+        if nodename.is_empty() {
+            return Err(super::err_empty_record());
+        }
+
+        Ok(nodename)
+    }
+
+    fn distro(self) -> Result<String> {
+        let uname = utsname()?;
+        let version = field(&uname.version);
+        // Example: "hrev57937 Jan  1 2024 06:40:45"
+        let revision = version.split_whitespace().next().unwrap_or_default();
+
+        Ok(format!(
+            "{} R{} ({})",
+            field(&uname.sysname),
+            field(&uname.release),
+            revision,
+        ))
+    }
+
+    #[inline(always)]
+    fn desktop_env(self) -> Result<DesktopEnv> {
+        Ok(DesktopEnv::Unknown("Haiku".to_string()))
+    }
+
+    #[inline(always)]
+    fn platform(self) -> Result<Platform> {
+        Ok(Platform::current())
+    }
+
+    #[inline(always)]
+    fn arch(self) -> Result<Arch> {
+        Ok(Arch::current())
+    }
+}
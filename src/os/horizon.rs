@@ -0,0 +1,58 @@
+//! Horizon target (Nintendo 3DS and Switch homebrew).
+//!
+//! Consoles have a single, unnamed user, so this returns fixed names rather
+//! than querying the system.
+
+use std::ffi::OsString;
+
+use crate::{
+    os::{Backend, Os},
+    Arch, DesktopEnv, Platform, Result,
+};
+
+impl Backend for Os {
+    #[inline(always)]
+    fn langs_raw(self) -> Result<Vec<String>> {
+        Ok(vec!["en-US".to_string()])
+    }
+
+    #[inline(always)]
+    fn realname(self) -> Result<OsString> {
+        Ok("Player".to_string().into())
+    }
+
+    #[inline(always)]
+    fn username(self) -> Result<OsString> {
+        Ok("player".to_string().into())
+    }
+
+    #[inline(always)]
+    fn devicename(self) -> Result<OsString> {
+        Ok(self.platform()?.to_string().into())
+    }
+
+    #[inline(always)]
+    fn hostname(self) -> Result<String> {
+        Ok("localhost".to_string())
+    }
+
+    #[inline(always)]
+    fn distro(self) -> Result<String> {
+        Ok(format!("Horizon ({})", self.platform()?))
+    }
+
+    #[inline(always)]
+    fn desktop_env(self) -> Result<DesktopEnv> {
+        Ok(DesktopEnv::Unknown("Horizon".to_string()))
+    }
+
+    #[inline(always)]
+    fn platform(self) -> Result<Platform> {
+        Ok(Platform::current())
+    }
+
+    #[inline(always)]
+    fn arch(self) -> Result<Arch> {
+        Ok(Arch::current())
+    }
+}
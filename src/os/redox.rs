@@ -8,7 +8,7 @@ use std::{borrow::Cow, ffi::OsString, fs, io::Error};
 use syscall::{call, error};
 
 use crate::{
-    os::{Os, Target},
+    os::{Backend, Os},
     Arch, DesktopEnv, Platform, Result,
 };
 
@@ -37,6 +37,23 @@ impl Passwd<'_> {
     }
 }
 
+/// Row in the Redox /etc/group file
+struct Group<'a>(Cow<'a, str>);
+
+impl Group<'_> {
+    fn column(&self, number: usize) -> Option<&str> {
+        self.0.split(';').nth(number)
+    }
+
+    fn name(&self) -> Option<String> {
+        self.column(0).map(ToString::to_string)
+    }
+
+    fn gid(&self) -> Option<usize> {
+        self.column(1)?.parse().ok()
+    }
+}
+
 struct Uname<'a>(Cow<'a, str>);
 
 impl Uname<'_> {
@@ -45,8 +62,7 @@ impl Uname<'_> {
     }
 
     fn machine_arch(&self) -> Option<Arch> {
-        // FIXME: Don't hardcode unknown arch
-        Some(Arch::Unknown(self.row(4)?.to_string()))
+        Some(Arch::from_machine(self.row(4)?))
     }
 }
 
@@ -77,6 +93,39 @@ fn passwd() -> Result<Passwd<'static>> {
     Err(super::err_missing_record())
 }
 
+fn group(gid: usize) -> Result<Group<'static>> {
+    let group_file = fs::read_to_string("/etc/group")?;
+
+    for group in group_file.lines() {
+        let group = Group(group.into());
+
+        if group.gid() == Some(gid) {
+            return Ok(Group(group.0.into_owned().into()));
+        }
+    }
+
+    Err(super::err_missing_record())
+}
+
+fn os_release(field: &str) -> Result<String> {
+    os_release_optional(field)?.ok_or_else(super::err_missing_record)
+}
+
+/// Like [`os_release()`], but `None` if the field is missing.
+fn os_release_optional(field: &str) -> Result<Option<String>> {
+    let release_file = fs::read_to_string("/etc/os-release")?;
+
+    for kv in release_file.lines() {
+        if let Some(kv) = kv.strip_prefix(field) {
+            if let Some(kv) = kv.strip_prefix('=') {
+                return Ok(Some(kv.trim_matches('\"').to_string()));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
 fn uname() -> Result<Uname<'static>> {
     let uname_file = fs::read_to_string("sys:uname")?;
 
@@ -89,8 +138,8 @@ fn hostname() -> Result<String> {
     Ok(hostname_file.lines().next().unwrap_or_default().to_string())
 }
 
-impl Target for Os {
-    fn langs(self) -> Result<String> {
+impl Backend for Os {
+    fn langs_raw(self) -> Result<Vec<String>> {
         super::unix_lang()
     }
 
@@ -104,6 +153,13 @@ impl Target for Os {
         Ok(passwd()?.username().unwrap_or_default().into())
     }
 
+    #[inline(always)]
+    fn primary_group(self) -> Result<OsString> {
+        let gid = passwd()?.gid().ok_or_else(super::err_missing_record)?;
+
+        Ok(group(gid)?.name().unwrap_or_default().into())
+    }
+
     #[inline(always)]
     fn devicename(self) -> Result<OsString> {
         hostname().map(OsString::from)
@@ -114,29 +170,37 @@ impl Target for Os {
         hostname()
     }
 
+    fn configured_hostname(self) -> Result<String> {
+        hostname()
+    }
+
     #[inline(always)]
     fn distro(self) -> Result<String> {
-        let release_file = fs::read_to_string("/etc/os-release")?;
+        os_release("PRETTY_NAME")
+    }
 
-        for kv in release_file.lines() {
-            if let Some(kv) = kv.strip_prefix("PRETTY_NAME=\"") {
-                if let Some(kv) = kv.strip_suffix('\"') {
-                    return Ok(kv.to_string());
-                }
-            }
-        }
+    #[inline(always)]
+    fn distro_id(self) -> Result<String> {
+        os_release("ID")
+    }
 
-        Err(super::err_missing_record())
+    #[inline(always)]
+    fn distro_id_like(self) -> Result<Vec<String>> {
+        Ok(os_release_optional("ID_LIKE")?
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect())
     }
 
     #[inline(always)]
-    fn desktop_env(self) -> DesktopEnv {
-        DesktopEnv::Orbital
+    fn desktop_env(self) -> Result<DesktopEnv> {
+        Ok(DesktopEnv::Orbital)
     }
 
     #[inline(always)]
-    fn platform(self) -> Platform {
-        Platform::Redox
+    fn platform(self) -> Result<Platform> {
+        Ok(Platform::current())
     }
 
     #[inline(always)]
@@ -2,20 +2,17 @@
 //!
 //! This can be used as a template when adding new target support.
 
-use std::{
-    ffi::OsString,
-    io::{Error, ErrorKind},
-};
+use std::ffi::OsString;
 
 use crate::{
-    os::{Os, Target},
+    os::{Backend, Os},
     Arch, DesktopEnv, Platform, Result,
 };
 
-impl Target for Os {
+impl Backend for Os {
     #[inline(always)]
-    fn langs(self) -> Result<String> {
-        Ok("en/US".to_string())
+    fn langs_raw(self) -> Result<Vec<String>> {
+        Ok(super::fake_langs())
     }
 
     #[inline(always)]
@@ -40,85 +37,21 @@ impl Target for Os {
 
     #[inline(always)]
     fn distro(self) -> Result<String> {
-        Ok(format!("Unknown {}", self.platform()))
+        Ok(format!("Unknown {}", self.platform()?))
     }
 
     #[inline(always)]
-    fn desktop_env(self) -> DesktopEnv {
-        DesktopEnv::Unknown("Unknown".to_string())
+    fn desktop_env(self) -> Result<DesktopEnv> {
+        Err(super::err_unsupported())
     }
 
     #[inline(always)]
-    fn platform(self) -> Platform {
-        if cfg!(target_os = "daku") {
-            Platform::Unknown("Daku".to_string())
-        } else if cfg!(target_os = "wasi") {
-            Platform::Unknown("WASI".to_string())
-        } else if cfg!(target_os = "windows") {
-            Platform::Windows
-        } else if cfg!(target_os = "macos") {
-            Platform::MacOS
-        } else if cfg!(target_os = "redox") {
-            Platform::Redox
-        } else if cfg!(target_os = "linux") {
-            Platform::Linux
-        } else if cfg!(target_os = "android") {
-            Platform::Android
-        } else if cfg!(target_os = "tvos") {
-            Platform::Unknown("tvOS".to_string())
-        } else if cfg!(target_os = "watchos") {
-            Platform::Unknown("watchOS".to_string())
-        } else if cfg!(target_os = "ios") {
-            Platform::Unknown("iOS".to_string())
-        } else if cfg!(target_os = "fuchsia") {
-            Platform::Fuchsia
-        } else if cfg!(target_os = "illumos") {
-            Platform::Illumos
-        } else if cfg!(any(
-            target_os = "dragonfly",
-            target_os = "freebsd",
-            target_os = "netbsd",
-            target_os = "openbsd",
-        )) {
-            Platform::Bsd
-        } else if cfg!(target_os = "haiku") {
-            Platform::Unknown("Haiku".to_string())
-        } else if cfg!(target_os = "vxworks") {
-            Platform::Unknown("VxWorks".to_string())
-        } else if cfg!(target_os = "nto") {
-            Platform::Unknown("QNX Neutrino".to_string())
-        } else if cfg!(target_os = "horizon") {
-            Platform::Nintendo
-        } else if cfg!(target_os = "vita") {
-            Platform::PlayStation
-        } else if cfg!(target_os = "hurd") {
-            Platform::Unknown("GNU Hurd".to_string())
-        } else if cfg!(target_os = "aix") {
-            Platform::Unknown("AIX OS".to_string())
-        } else if cfg!(target_os = "espidf") {
-            Platform::Unknown("ESP-IDF".to_string())
-        } else if cfg!(target_os = "emscripten") {
-            Platform::Unknown("Emscripten".to_string())
-        } else if cfg!(target_os = "solaris") {
-            Platform::Unknown("Solaris".to_string())
-        } else if cfg!(target_os = "l4re") {
-            Platform::Unknown("L4 Runtime Environment".to_string())
-        } else {
-            Platform::Unknown("Unknown".to_string())
-        }
+    fn platform(self) -> Result<Platform> {
+        Ok(Platform::current())
     }
 
     #[inline(always)]
     fn arch(self) -> Result<Arch> {
-        Ok(if cfg!(target_pointer_width = "64") {
-            Arch::Wasm64
-        } else if cfg!(target_pointer_width = "32") {
-            Arch::Wasm32
-        } else {
-            return Err(Error::new(
-                ErrorKind::Other, // FIXME: WhoAmI 2.0, Unsupported
-                "Unexpected pointer width for target platform",
-            ));
-        })
+        Ok(Arch::current())
     }
 }
@@ -1,37 +1,49 @@
 #[cfg(target_os = "illumos")]
 use std::convert::TryInto;
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+use std::os::raw::c_uchar;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::os::unix::ffi::OsStrExt;
+#[cfg(target_os = "macos")]
+use std::sync::atomic::{AtomicPtr, Ordering};
 #[cfg(any(
     target_os = "linux",
+    target_os = "ios",
+    target_os = "macos",
     target_os = "dragonfly",
     target_os = "freebsd",
     target_os = "netbsd",
-    target_os = "openbsd",
-    target_os = "illumos",
 ))]
-use std::env;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{
-    ffi::{c_void, CStr, OsString},
+    env,
+    ffi::{c_void, CStr, CString, OsString},
     fs,
     io::{Error, ErrorKind},
     mem,
     os::{
-        raw::{c_char, c_int},
+        raw::{c_char, c_int, c_long},
         unix::ffi::OsStringExt,
     },
-    slice,
-};
-#[cfg(target_os = "macos")]
-use std::{
-    os::{
-        raw::{c_long, c_uchar},
-        unix::ffi::OsStrExt,
-    },
-    ptr::null_mut,
+    ptr, slice, str,
 };
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+use std::{path::Path, process::Command};
 
+#[cfg(target_os = "linux")]
+use crate::LibcKind;
 use crate::{
-    os::{Os, Target},
-    Arch, DesktopEnv, Platform, Result,
+    conversions,
+    os::{Backend, Os},
+    Arch, DesktopEnv, MeasurementSystem, Passwd, Platform, Result,
+    HOSTNAME_MAX_LEN,
 };
 
 #[cfg(target_os = "linux")]
@@ -47,6 +59,7 @@ struct PassWd {
 }
 
 #[cfg(any(
+    target_os = "ios",
     target_os = "macos",
     target_os = "dragonfly",
     target_os = "freebsd",
@@ -82,6 +95,14 @@ struct PassWd {
     pw_shell: *const c_void,
 }
 
+#[repr(C)]
+struct Group {
+    gr_name: *const c_void,
+    gr_passwd: *const c_void,
+    gr_gid: u32,
+    gr_mem: *const *const c_void,
+}
+
 #[cfg(target_os = "illumos")]
 extern "system" {
     fn getpwuid_r(
@@ -90,10 +111,17 @@ extern "system" {
         buf: *mut c_void,
         buflen: c_int,
     ) -> *mut PassWd;
+    fn getpwnam_r(
+        name: *const c_char,
+        pwd: *mut PassWd,
+        buf: *mut c_void,
+        buflen: c_int,
+    ) -> *mut PassWd;
 }
 
 #[cfg(any(
     target_os = "linux",
+    target_os = "ios",
     target_os = "macos",
     target_os = "dragonfly",
     target_os = "freebsd",
@@ -108,16 +136,133 @@ extern "system" {
         buflen: usize,
         result: *mut *mut PassWd,
     ) -> i32;
+    fn getpwnam_r(
+        name: *const c_char,
+        pwd: *mut PassWd,
+        buf: *mut c_void,
+        buflen: usize,
+        result: *mut *mut PassWd,
+    ) -> i32;
+}
+
+extern "system" {
+    // illumos only exports the POSIX signature under this name
+    #[cfg_attr(target_os = "illumos", link_name = "__posix_getgrgid_r")]
+    fn getgrgid_r(
+        gid: u32,
+        grp: *mut Group,
+        buf: *mut c_void,
+        buflen: usize,
+        result: *mut *mut Group,
+    ) -> i32;
 }
 
 extern "system" {
     fn geteuid() -> u32;
+    fn isatty(fd: c_int) -> c_int;
     fn gethostname(name: *mut c_void, len: usize) -> i32;
+    fn sysconf(name: c_int) -> c_long;
+    fn getaddrinfo(
+        node: *const c_char,
+        service: *const c_char,
+        hints: *const AddrInfo,
+        res: *mut *mut AddrInfo,
+    ) -> c_int;
+    fn freeaddrinfo(res: *mut AddrInfo);
 }
 
-#[cfg(target_os = "macos")]
+#[cfg(not(target_os = "illumos"))]
+const AI_CANONNAME: c_int = 0x0002;
+#[cfg(target_os = "illumos")]
+const AI_CANONNAME: c_int = 0x0010;
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct AddrInfo {
+    ai_flags: c_int,
+    ai_family: c_int,
+    ai_socktype: c_int,
+    ai_protocol: c_int,
+    ai_addrlen: u32,
+    ai_addr: *mut c_void,
+    ai_canonname: *mut c_char,
+    ai_next: *mut AddrInfo,
+}
+
+#[cfg(not(target_os = "linux"))]
+#[repr(C)]
+struct AddrInfo {
+    ai_flags: c_int,
+    ai_family: c_int,
+    ai_socktype: c_int,
+    ai_protocol: c_int,
+    ai_addrlen: u32,
+    ai_canonname: *mut c_char,
+    ai_addr: *mut c_void,
+    ai_next: *mut AddrInfo,
+}
+
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+))]
+extern "system" {
+    fn sysctlbyname(
+        name: *const c_char,
+        oldp: *mut c_void,
+        oldlenp: *mut usize,
+        newp: *mut c_void,
+        newlen: usize,
+    ) -> c_int;
+}
+
+#[cfg(target_os = "openbsd")]
+extern "system" {
+    fn sysctl(
+        name: *const c_int,
+        namelen: u32,
+        oldp: *mut c_void,
+        oldlenp: *mut usize,
+        newp: *mut c_void,
+        newlen: usize,
+    ) -> c_int;
+}
+
+#[cfg(target_os = "linux")]
+const SC_NPROCESSORS_ONLN: c_int = 84;
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+))]
+const SC_NPROCESSORS_ONLN: c_int = 58;
+#[cfg(target_os = "netbsd")]
+const SC_NPROCESSORS_ONLN: c_int = 1002;
+#[cfg(target_os = "openbsd")]
+const SC_NPROCESSORS_ONLN: c_int = 503;
+#[cfg(target_os = "illumos")]
+const SC_NPROCESSORS_ONLN: c_int = 15;
+#[cfg(any(target_os = "linux", target_os = "illumos"))]
+const LC_NUMERIC_MASK: c_int = 1 << 1;
+#[cfg(any(target_os = "dragonfly", target_os = "freebsd"))]
+const LC_NUMERIC_MASK: c_int = 1 << 3;
+#[cfg(target_os = "linux")]
+const RADIXCHAR: c_int = 0x10000;
+#[cfg(any(target_os = "dragonfly", target_os = "freebsd"))]
+const RADIXCHAR: c_int = 50;
+#[cfg(target_os = "illumos")]
+const RADIXCHAR: c_int = 39;
+#[cfg(target_os = "illumos")]
+const SC_PAGESIZE: c_int = 11;
+#[cfg(target_os = "illumos")]
+const SC_PHYS_PAGES: c_int = 500;
+
+#[cfg(any(target_os = "ios", target_os = "macos"))]
 #[link(name = "CoreFoundation", kind = "framework")]
-#[link(name = "SystemConfiguration", kind = "framework")]
 extern "system" {
     fn CFStringGetCString(
         the_string: *mut c_void,
@@ -130,16 +275,74 @@ extern "system" {
         length: c_long,
         encoding: u32,
     ) -> c_long;
+    fn CFLocaleCopyPreferredLanguages() -> *mut c_void;
+    fn CFArrayGetCount(the_array: *mut c_void) -> c_long;
+    fn CFArrayGetValueAtIndex(
+        the_array: *mut c_void,
+        index: c_long,
+    ) -> *mut c_void;
+    fn CFRetain(cf: *const c_void) -> *const c_void;
+    fn CFRelease(cf: *const c_void);
+    fn CFLocaleCopyCurrent() -> *mut c_void;
+    #[cfg(target_os = "macos")]
+    fn CFStringCreateWithCString(
+        alloc: *const c_void,
+        c_str: *const u8,
+        encoding: u32,
+    ) -> *mut c_void;
+    fn CFLocaleGetValue(locale: *mut c_void, key: *const c_void)
+        -> *mut c_void;
+
+    static kCFLocaleMeasurementSystem: *const c_void;
+    static kCFLocaleDecimalSeparator: *const c_void;
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+))]
+extern "C" {
+    fn newlocale(
+        mask: c_int,
+        locale: *const c_char,
+        base: *mut c_void,
+    ) -> *mut c_void;
+    fn freelocale(locale: *mut c_void);
+    fn nl_langinfo_l(item: c_int, locale: *mut c_void) -> *const c_char;
+}
+
+#[cfg(target_os = "macos")]
+const RTLD_LAZY: c_int = 0x1;
+
+#[cfg(target_os = "macos")]
+extern "system" {
+    fn dlopen(path: *const c_char, mode: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "SystemConfiguration", kind = "framework")]
+extern "system" {
+    fn SCDynamicStoreCreate(
+        allocator: *const c_void,
+        name: *mut c_void,
+        callout: *mut c_void,
+        context: *mut c_void,
+    ) -> *mut c_void;
     fn SCDynamicStoreCopyComputerName(
         store: *mut c_void,
         encoding: *mut u32,
     ) -> *mut c_void;
-    fn CFRelease(cf: *const c_void);
 }
 
 enum Name {
     User,
     Real,
+    Gecos,
+    Group,
+    Home,
 }
 
 unsafe fn strlen(cs: *const c_void) -> usize {
@@ -202,7 +405,7 @@ fn os_from_cstring(string: *const c_void) -> Result<OsString> {
     Ok(OsString::from_vec(slice.to_vec()))
 }
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "ios", target_os = "macos"))]
 fn os_from_cfstring(string: *mut c_void) -> OsString {
     if string.is_null() {
         return "".to_string().into();
@@ -231,10 +434,632 @@ fn os_from_cfstring(string: *mut c_void) -> OsString {
     }
 }
 
+/// Session for querying the computer name, created on first use and kept for
+/// the life of the process
+#[cfg(target_os = "macos")]
+static DYNAMIC_STORE: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+/// Get the shared dynamic store session, or null if it can't be created (in
+/// which case a temporary session is used for each query).
+#[cfg(target_os = "macos")]
+fn dynamic_store() -> *mut c_void {
+    let store = DYNAMIC_STORE.load(Ordering::Acquire);
+
+    if !store.is_null() {
+        return store;
+    }
+
+    let store = unsafe {
+        let name = CFStringCreateWithCString(
+            ptr::null(),
+            b"whoami\0".as_ptr(),
+            134_217_984, /* UTF8 */
+        );
+
+        if name.is_null() {
+            return ptr::null_mut();
+        }
+
+        let store = SCDynamicStoreCreate(
+            ptr::null(),
+            name,
+            ptr::null_mut(),
+            ptr::null_mut(),
+        );
+
+        CFRelease(name);
+        store
+    };
+
+    if store.is_null() {
+        return store;
+    }
+
+    // Another thread may have created a session first
+    match DYNAMIC_STORE.compare_exchange(
+        ptr::null_mut(),
+        store,
+        Ordering::AcqRel,
+        Ordering::Acquire,
+    ) {
+        Ok(_) => store,
+        Err(existing) => {
+            unsafe { CFRelease(store) };
+            existing
+        }
+    }
+}
+
+/// Get the user's preferred languages (`AppleLanguages`), most preferred
+/// first.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+fn apple_langs() -> Vec<String> {
+    let mut langs = Vec::new();
+
+    unsafe {
+        let array = CFLocaleCopyPreferredLanguages();
+
+        if array.is_null() {
+            return langs;
+        }
+
+        for index in 0..CFArrayGetCount(array) {
+            let string = CFArrayGetValueAtIndex(array, index);
+
+            if string.is_null() {
+                continue;
+            }
+
+            // Array elements are borrowed, `os_from_cfstring()` releases
+            CFRetain(string);
+
+            if let Ok(lang) = os_from_cfstring(string).into_string() {
+                if !lang.is_empty() {
+                    langs.push(lang);
+                }
+            }
+        }
+
+        CFRelease(array);
+    }
+
+    langs
+}
+
+/// Get a string value of the user's current locale.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+fn apple_locale_value(key: *const c_void) -> Option<String> {
+    unsafe {
+        let locale = CFLocaleCopyCurrent();
+
+        if locale.is_null() {
+            return None;
+        }
+
+        let value = CFLocaleGetValue(locale, key);
+        let value = if value.is_null() {
+            None
+        } else {
+            // Values are borrowed, `os_from_cfstring()` releases
+            CFRetain(value);
+            os_from_cfstring(value).into_string().ok()
+        };
+
+        CFRelease(locale);
+        value.filter(|value| !value.is_empty())
+    }
+}
+
+/// Get the measurement system from the territory of the measurement locale
+/// set in the environment (US customary units for the United States, Liberia
+/// and Myanmar, and metric elsewhere).
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+fn measurement_system() -> Result<MeasurementSystem> {
+    let locale = ["LC_ALL", "LC_MEASUREMENT", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|locale| !locale.is_empty())
+        .ok_or_else(super::err_missing_record)?;
+    // Example: "en_US.UTF-8"
+    let territory = locale
+        .split(|c| c == '.' || c == '@')
+        .next()
+        .unwrap_or_default()
+        .splitn(2, '_')
+        .nth(1)
+        .ok_or_else(super::err_missing_record)?;
+
+    Ok(match territory {
+        "US" | "LR" | "MM" => MeasurementSystem::Uscs,
+        _ => MeasurementSystem::Metric,
+    })
+}
+
+/// Get the decimal separator of the numeric locale set in the environment.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "illumos",
+))]
+fn decimal_separator() -> Result<char> {
+    let locale = unsafe {
+        newlocale(LC_NUMERIC_MASK, b"\0".as_ptr().cast(), ptr::null_mut())
+    };
+
+    if locale.is_null() {
+        return Err(Error::last_os_error());
+    }
+
+    let radix = unsafe { nl_langinfo_l(RADIXCHAR, locale) };
+    // Copy the separator out before freeing the locale that owns the string
+    let separator = if radix.is_null() {
+        None
+    } else {
+        unsafe { CStr::from_ptr(radix) }
+            .to_str()
+            .ok()
+            .and_then(|radix| radix.chars().next())
+    };
+
+    unsafe { freelocale(locale) };
+
+    separator.ok_or_else(super::err_empty_record)
+}
+
+/// Get the scale factor of the main display, as the ratio of pixels to points.
+///
+/// CoreGraphics is loaded at runtime, so that programs aren't linked to it
+/// unless they call this.
+#[cfg(target_os = "macos")]
+fn display_scale() -> Result<f32> {
+    type CGMainDisplayID = unsafe extern "system" fn() -> u32;
+    type CGDisplayCopyDisplayMode =
+        unsafe extern "system" fn(display: u32) -> *mut c_void;
+    type CGDisplayModeGetWidth =
+        unsafe extern "system" fn(mode: *mut c_void) -> usize;
+    type CGDisplayModeRelease = unsafe extern "system" fn(mode: *mut c_void);
+
+    // Never unloaded, as frameworks can't be safely unloaded
+    let core_graphics = unsafe {
+        dlopen(
+            b"/System/Library/Frameworks/CoreGraphics.framework/CoreGraphics\0"
+                .as_ptr()
+                .cast(),
+            RTLD_LAZY,
+        )
+    };
+
+    if core_graphics.is_null() {
+        return Err(super::err_unsupported());
+    }
+
+    let symbol = |name: &[u8]| {
+        let symbol = unsafe { dlsym(core_graphics, name.as_ptr().cast()) };
+
+        if symbol.is_null() {
+            return Err(super::err_unsupported());
+        }
+
+        Ok(symbol)
+    };
+    let main_display_id: CGMainDisplayID =
+        unsafe { mem::transmute(symbol(b"CGMainDisplayID\0")?) };
+    let copy_display_mode: CGDisplayCopyDisplayMode =
+        unsafe { mem::transmute(symbol(b"CGDisplayCopyDisplayMode\0")?) };
+    let get_width: CGDisplayModeGetWidth =
+        unsafe { mem::transmute(symbol(b"CGDisplayModeGetWidth\0")?) };
+    let get_pixel_width: CGDisplayModeGetWidth =
+        unsafe { mem::transmute(symbol(b"CGDisplayModeGetPixelWidth\0")?) };
+    let release: CGDisplayModeRelease =
+        unsafe { mem::transmute(symbol(b"CGDisplayModeRelease\0")?) };
+    let mode = unsafe { copy_display_mode(main_display_id()) };
+
+    if mode.is_null() {
+        return Err(super::err_missing_record());
+    }
+
+    let (points, pixels) = unsafe { (get_width(mode), get_pixel_width(mode)) };
+
+    unsafe { release(mode) };
+
+    if points == 0 {
+        return Err(super::err_empty_record());
+    }
+
+    Ok(pixels as f32 / points as f32)
+}
+
+/// Get the scale factor from the toolkit environment variables or the X
+/// resources file.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+fn display_scale() -> Result<f32> {
+    let positive = |value: &str| {
+        value
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite() && *value > 0.0)
+    };
+
+    for name in &["GDK_SCALE", "QT_SCALE_FACTOR"] {
+        if let Some(scale) = env::var(name).ok().and_then(|v| positive(&v)) {
+            return Ok(scale);
+        }
+    }
+
+    let home = env::var_os("HOME").ok_or_else(super::err_missing_record)?;
+    let resources = fs::read_to_string(Path::new(&home).join(".Xresources"))?;
+
+    // Example: "Xft.dpi: 192"
+    resources
+        .lines()
+        .find_map(|line| {
+            let mut pair = line.splitn(2, ':');
+
+            if pair.next()?.trim() != "Xft.dpi" {
+                return None;
+            }
+
+            positive(pair.next()?)
+        })
+        .map(|dpi| dpi / 96.0)
+        .ok_or_else(super::err_missing_record)
+}
+
+/// Match a freedesktop session or desktop name to a [`DesktopEnv`].
+///
+/// Display server suffixes (as in "gnome-xorg" or "plasmawayland") are
+/// ignored, so GNOME on Wayland and GNOME on X11 are both recognized as GNOME.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+fn desktop_env_from_name(name: &str) -> Option<DesktopEnv> {
+    let name = name.to_ascii_lowercase();
+    let name = name
+        .trim_start_matches("x-")
+        .trim_end_matches("wayland")
+        .trim_end_matches("xorg")
+        .trim_end_matches("x11")
+        .trim_end_matches('-');
+
+    Some(match name {
+        "gnome" | "gnome-classic" => DesktopEnv::Gnome,
+        "lxde" => DesktopEnv::Lxde,
+        "openbox" => DesktopEnv::Openbox,
+        "mate" => DesktopEnv::Mate,
+        "xfce" => DesktopEnv::Xfce,
+        "kde" | "plasma" | "plasma5" => DesktopEnv::Kde,
+        "cinnamon" => DesktopEnv::Cinnamon,
+        "i3" => DesktopEnv::I3,
+        "ubuntu" => DesktopEnv::Ubuntu,
+        "cosmic" => DesktopEnv::Cosmic,
+        "pantheon" => DesktopEnv::Pantheon,
+        "cde" | "dtwm" => DesktopEnv::Cde,
+        "twm" => DesktopEnv::Twm,
+        "fvwm" | "fvwm2" | "fvwm3" => DesktopEnv::Fvwm,
+        // TODO: Other Linux Desktop Environments
+        _ => return None,
+    })
+}
+
+/// Detect the desktop environment from the session environment variables, as
+/// returned by `var`.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+fn desktop_env_from_vars(
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<DesktopEnv> {
+    let session = var("DESKTOP_SESSION");
+    let current = var("XDG_CURRENT_DESKTOP");
+    let xdg_session = var("XDG_SESSION_DESKTOP");
+    // `DESKTOP_SESSION` first, then each of the `XDG_CURRENT_DESKTOP` names,
+    // with `XDG_SESSION_DESKTOP` as the tiebreaker.
+    let mut names = session
+        .iter()
+        .map(String::as_str)
+        .chain(current.iter().flat_map(|names| names.split(':')))
+        .chain(xdg_session.iter().map(String::as_str))
+        .filter(|name| !name.is_empty());
+    let first = names.next()?;
+
+    Some(
+        Some(first)
+            .into_iter()
+            .chain(names)
+            .find_map(desktop_env_from_name)
+            .unwrap_or_else(|| DesktopEnv::Unknown(first.to_string())),
+    )
+}
+
+/// Returns true if the environment indicates a graphical session: a display
+/// server, or an `XDG_SESSION_TYPE` other than "tty" or "unspecified".
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+fn graphical_session() -> bool {
+    env::var_os("DISPLAY").is_some()
+        || env::var_os("WAYLAND_DISPLAY").is_some()
+        || env::var_os("XDG_SESSION_TYPE").map_or(false, |session_type| {
+            !session_type.is_empty()
+                && session_type != "tty"
+                && session_type != "unspecified"
+        })
+}
+
+/// Returns true if running inside a Flatpak or Snap sandbox.
+#[cfg(target_os = "linux")]
+fn sandboxed() -> bool {
+    Path::new("/.flatpak-info").exists()
+        || env::var_os("container").map_or(false, |c| c == "flatpak")
+        || env::var_os("SNAP").is_some()
+}
+
+/// Get the parent process ID of `pid` (or "self").
+#[cfg(target_os = "linux")]
+fn parent_pid(pid: &str) -> Option<u32> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+
+    // The command name may contain spaces, so skip past its closing
+    // parenthesis; the state comes next, then the parent process ID.
+    stat[stat.rfind(')')? + 1..]
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+/// Detect the desktop environment from the environment variables of ancestor
+/// processes, for when they were cleared (as by `sudo` with `env_reset`).
+///
+/// Only done in a graphical session, so that headless programs don't read the
+/// environment of every ancestor process.
+#[cfg(target_os = "linux")]
+fn desktop_env_from_ancestors() -> Option<DesktopEnv> {
+    // Sandboxes pass the session environment variables through, and may deny
+    // (and log) reading the environment of processes outside of the sandbox
+    if !graphical_session() || sandboxed() {
+        return None;
+    }
+
+    let mut pid = parent_pid("self")?;
+
+    // Stop before init
+    while pid > 1 {
+        if let Ok(environ) = fs::read(format!("/proc/{}/environ", pid)) {
+            let var = |name: &str| {
+                environ.split(|b| *b == 0).find_map(|entry| {
+                    let mut pair = entry.splitn(2, |b| *b == b'=');
+
+                    if pair.next()? != name.as_bytes() {
+                        return None;
+                    }
+
+                    Some(String::from_utf8_lossy(pair.next()?).into_owned())
+                })
+            };
+
+            if let Some(env) = desktop_env_from_vars(var) {
+                return Some(env);
+            }
+        }
+
+        pid = parent_pid(&pid.to_string())?;
+    }
+
+    None
+}
+
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+fn desktop_env_from_ancestors() -> Option<DesktopEnv> {
+    None
+}
+
+/// Detect the desktop environment from a running shell or compositor process
+/// owned by the current user, for when a display is available but the desktop
+/// session variables aren't set (as for some systemd user services).
+///
+/// Only done in a graphical session, since it scans every process.
+#[cfg(target_os = "linux")]
+fn desktop_env_from_procs() -> Option<DesktopEnv> {
+    // Host processes may not be visible from inside a sandbox
+    if !graphical_session() || sandboxed() {
+        return None;
+    }
+
+    find_user_proc(|comm| {
+        Some(match comm {
+            "gnome-shell" => DesktopEnv::Gnome,
+            "plasmashell" => DesktopEnv::Kde,
+            "sway" => DesktopEnv::Unknown("sway".to_string()),
+            "Hyprland" => DesktopEnv::Unknown("Hyprland".to_string()),
+            "dtwm" => DesktopEnv::Cde,
+            _ => return None,
+        })
+    })
+}
+
+/// Find the first process owned by the current user for which `f` returns
+/// `Some`, passing the process name.
+#[cfg(target_os = "linux")]
+fn find_user_proc<T>(mut f: impl FnMut(&str) -> Option<T>) -> Option<T> {
+    use std::os::unix::fs::MetadataExt;
+
+    let uid = unsafe { geteuid() };
+
+    fs::read_dir("/proc").ok()?.flatten().find_map(|entry| {
+        let name = entry.file_name();
+
+        if !name.as_bytes().iter().all(u8::is_ascii_digit)
+            || entry.metadata().ok()?.uid() != uid
+        {
+            return None;
+        }
+
+        let comm = fs::read_to_string(entry.path().join("comm")).ok()?;
+
+        f(comm.trim_end())
+    })
+}
+
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+fn desktop_env_from_procs() -> Option<DesktopEnv> {
+    None
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+fn desktop_env_version(env: DesktopEnv) -> Result<String> {
+    let program = match env {
+        DesktopEnv::Gnome | DesktopEnv::Ubuntu => "gnome-shell",
+        _ if env::var_os("GNOME_SHELL_SESSION_MODE").is_some() => "gnome-shell",
+        DesktopEnv::Kde => "plasmashell",
+        DesktopEnv::Xfce => "xfce4-session",
+        DesktopEnv::Mate => "mate-session",
+        DesktopEnv::Cinnamon => "cinnamon",
+        _ => return Err(super::err_unsupported()),
+    };
+    let output = Command::new(program).arg("--version").output()?;
+
+    if !output.status.success() {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("`{} --version` failed", program),
+        ));
+    }
+
+    // For example, "GNOME Shell 45.2" or "xfce4-session 4.18.1 (Xfce 4.18)"
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+        .map(ToString::to_string)
+        .ok_or_else(super::err_missing_record)
+}
+
+fn cstring_from_username(username: &str) -> Result<CString> {
+    CString::new(username).map_err(|_| {
+        Error::new(ErrorKind::InvalidInput, "Username contains NUL byte")
+    })
+}
+
+/// Get the username from the environment, for when the user has no passwd
+/// entry (as in some containers and chroots).
+fn username_from_env() -> Option<OsString> {
+    ["LOGNAME", "USER"]
+        .iter()
+        .filter_map(env::var_os)
+        .find(|username| !username.is_empty())
+}
+
+/// Append a C string to `buf`, without allocating an intermediate `String`.
+fn push_cstring(buf: &mut String, string: *const c_void) -> Result<()> {
+    if string.is_null() {
+        return Err(super::err_null_record());
+    }
+
+    let slice = unsafe { CStr::from_ptr(string.cast()) }.to_bytes();
+
+    if slice.is_empty() {
+        return Err(super::err_empty_record());
+    }
+
+    buf.push_str(
+        str::from_utf8(slice)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?,
+    );
+
+    Ok(())
+}
+
 // This function must allocate, because a slice or `Cow<OsStr>` would still
 // reference `passwd` which is dropped when this function returns.
+//
+// Looks up the effective user if `user` is `None`.
 #[inline(always)]
-fn getpwuid(name: Name) -> Result<OsString> {
+fn getpwuid(user: Option<&CStr>, name: Name) -> Result<OsString> {
+    with_passwd(user, |passwd| match name {
+        Name::User => os_from_cstring(passwd.pw_name),
+        Name::Real => os_from_cstring_gecos(passwd.pw_gecos),
+        Name::Gecos => os_from_cstring(passwd.pw_gecos),
+        Name::Group => getgrgid(passwd.pw_gid),
+        Name::Home => os_from_cstring(passwd.pw_dir),
+    })
+}
+
+/// Get every field of the effective user's passwd record with one lookup.
+fn passwd() -> Result<Passwd> {
+    with_passwd(None, |passwd| {
+        Ok(Passwd {
+            name: os_from_cstring(passwd.pw_name)?,
+            realname: os_from_cstring_gecos(passwd.pw_gecos)
+                .unwrap_or_default(),
+            uid: passwd.pw_uid,
+            gid: passwd.pw_gid,
+            home: os_from_cstring(passwd.pw_dir).unwrap_or_default(),
+            shell: os_from_cstring(passwd.pw_shell).unwrap_or_default(),
+        })
+    })
+}
+
+// Calls `f` with the passwd record, which is only valid until `f` returns.
+//
+// Looks up the effective user if `user` is `None`.
+#[inline(always)]
+fn with_passwd<T>(
+    user: Option<&CStr>,
+    f: impl FnOnce(&PassWd) -> Result<T>,
+) -> Result<T> {
     const BUF_SIZE: usize = 16_384; // size from the man page
     let mut buffer = mem::MaybeUninit::<[u8; BUF_SIZE]>::uninit();
     let mut passwd = mem::MaybeUninit::<PassWd>::uninit();
@@ -243,6 +1068,7 @@ fn getpwuid(name: Name) -> Result<OsString> {
     let passwd = unsafe {
         #[cfg(any(
             target_os = "linux",
+            target_os = "ios",
             target_os = "macos",
             target_os = "dragonfly",
             target_os = "freebsd",
@@ -251,51 +1077,583 @@ fn getpwuid(name: Name) -> Result<OsString> {
         ))]
         {
             let mut _passwd = mem::MaybeUninit::<*mut PassWd>::uninit();
-            let ret = getpwuid_r(
-                geteuid(),
-                passwd.as_mut_ptr(),
-                buffer.as_mut_ptr() as *mut c_void,
-                BUF_SIZE,
-                _passwd.as_mut_ptr(),
-            );
+            let ret = if let Some(user) = user {
+                getpwnam_r(
+                    user.as_ptr(),
+                    passwd.as_mut_ptr(),
+                    buffer.as_mut_ptr() as *mut c_void,
+                    BUF_SIZE,
+                    _passwd.as_mut_ptr(),
+                )
+            } else {
+                getpwuid_r(
+                    geteuid(),
+                    passwd.as_mut_ptr(),
+                    buffer.as_mut_ptr() as *mut c_void,
+                    BUF_SIZE,
+                    _passwd.as_mut_ptr(),
+                )
+            };
+
+            if ret != 0 {
+                return Err(Error::last_os_error());
+            }
+
+            let _passwd = _passwd.assume_init();
+
+            if _passwd.is_null() {
+                return Err(super::err_null_record());
+            }
+            passwd.assume_init()
+        }
+
+        #[cfg(target_os = "illumos")]
+        {
+            let ret = if let Some(user) = user {
+                getpwnam_r(
+                    user.as_ptr(),
+                    passwd.as_mut_ptr(),
+                    buffer.as_mut_ptr() as *mut c_void,
+                    BUF_SIZE.try_into().unwrap_or(c_int::max_value()),
+                )
+            } else {
+                getpwuid_r(
+                    geteuid(),
+                    passwd.as_mut_ptr(),
+                    buffer.as_mut_ptr() as *mut c_void,
+                    BUF_SIZE.try_into().unwrap_or(c_int::max_value()),
+                )
+            };
+
+            if ret.is_null() {
+                let error = Error::last_os_error();
+
+                // No error is set when the user doesn't exist
+                if error.raw_os_error() == Some(0) {
+                    return Err(super::err_null_record());
+                }
+
+                return Err(error);
+            }
+            passwd.assume_init()
+        }
+    };
+
+    f(&passwd)
+}
+
+// This function must allocate, because a slice or `Cow<OsStr>` would still
+// reference `group` which is dropped when this function returns.
+#[inline(always)]
+fn getgrgid(gid: u32) -> Result<OsString> {
+    const ERANGE: i32 = 34;
+
+    let mut buffer = Vec::<u8>::with_capacity(16_384); // size from the man page
+    let mut group = mem::MaybeUninit::<Group>::uninit();
+
+    // Get Group `struct`, growing the buffer until the record fits.
+    let group = loop {
+        let mut result = mem::MaybeUninit::<*mut Group>::uninit();
+        let ret = unsafe {
+            getgrgid_r(
+                gid,
+                group.as_mut_ptr(),
+                buffer.as_mut_ptr().cast(),
+                buffer.capacity(),
+                result.as_mut_ptr(),
+            )
+        };
+
+        if ret == ERANGE {
+            buffer.reserve(buffer.capacity() * 2);
+            continue;
+        }
+
+        if ret != 0 {
+            return Err(Error::from_raw_os_error(ret));
+        }
+
+        if unsafe { result.assume_init() }.is_null() {
+            return Err(super::err_null_record());
+        }
+
+        break unsafe { group.assume_init() };
+    };
+
+    os_from_cstring(group.gr_name)
+}
+
+/// Read an unsigned integer (of either 32 or 64 bits) with `sysctlbyname()`.
+///
+/// `name` must be NUL-terminated.
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+))]
+fn sysctl_u64(name: &[u8]) -> Result<u64> {
+    let mut buf = [0u8; 8];
+    let mut len = buf.len();
+    let ret = unsafe {
+        sysctlbyname(
+            name.as_ptr().cast(),
+            buf.as_mut_ptr().cast(),
+            &mut len,
+            ptr::null_mut(),
+            0,
+        )
+    };
+
+    if ret == -1 {
+        return Err(Error::last_os_error());
+    }
+
+    match len {
+        4 => Ok(u32::from_ne_bytes([buf[0], buf[1], buf[2], buf[3]]).into()),
+        8 => Ok(u64::from_ne_bytes(buf)),
+        _ => Err(Error::new(ErrorKind::InvalidData, "Unexpected sysctl size")),
+    }
+}
+
+/// Get the boot time from the `kern.boottime` sysctl.
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+))]
+fn boot_time() -> Result<SystemTime> {
+    let mut buf = [0u8; 16];
+    let mut len = buf.len();
+    let ret = unsafe {
+        sysctlbyname(
+            b"kern.boottime\0".as_ptr().cast(),
+            buf.as_mut_ptr().cast(),
+            &mut len,
+            ptr::null_mut(),
+            0,
+        )
+    };
+
+    if ret == -1 {
+        return Err(Error::last_os_error());
+    }
+
+    // A `struct timeval`, where `time_t` is 64 bits except on 32-bit x86
+    // FreeBSD
+    let secs = match len {
+        8 => i32::from_ne_bytes([buf[0], buf[1], buf[2], buf[3]]).into(),
+        16 => i64::from_ne_bytes([
+            buf[0], buf[1], buf[2], buf[3], buf[4], buf[5], buf[6], buf[7],
+        ]),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Unexpected sysctl size",
+            ))
+        }
+    };
+
+    if secs < 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Boot time before 1970",
+        ));
+    }
+
+    Ok(UNIX_EPOCH + Duration::from_secs(secs as u64))
+}
+
+/// Get the boot time from the `btime` field of `/proc/stat`.
+#[cfg(target_os = "linux")]
+fn boot_time() -> Result<SystemTime> {
+    let stat = fs::read_to_string("/proc/stat")?;
+    // Example: "btime 1700000000"
+    let secs = stat
+        .lines()
+        .find_map(|line| {
+            let mut words = line.split_whitespace();
+
+            if words.next()? != "btime" {
+                return None;
+            }
+
+            words.next()?.parse::<u64>().ok()
+        })
+        .ok_or_else(super::err_missing_record)?;
+
+    Ok(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Read a string with `sysctlbyname()`.
+///
+/// `name` must be NUL-terminated.
+#[cfg(target_os = "macos")]
+fn sysctl_os_string(name: &[u8]) -> Result<OsString> {
+    let mut len = 0;
+    let ret = unsafe {
+        sysctlbyname(
+            name.as_ptr().cast(),
+            ptr::null_mut(),
+            &mut len,
+            ptr::null_mut(),
+            0,
+        )
+    };
+
+    if ret == -1 {
+        return Err(Error::last_os_error());
+    }
+
+    let mut buf = Vec::<u8>::with_capacity(len);
+    let ret = unsafe {
+        sysctlbyname(
+            name.as_ptr().cast(),
+            buf.as_mut_ptr().cast(),
+            &mut len,
+            ptr::null_mut(),
+            0,
+        )
+    };
+
+    if ret == -1 {
+        return Err(Error::last_os_error());
+    }
+
+    unsafe { buf.set_len(strlen(buf.as_ptr().cast()).min(len)) };
+
+    if buf.is_empty() {
+        return Err(super::err_empty_record());
+    }
+
+    Ok(OsString::from_vec(buf))
+}
+
+/// Read the `MemTotal` field of `/proc/meminfo` in bytes.
+#[cfg(target_os = "linux")]
+fn memory_total() -> Result<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo")?;
+    let err = || Error::new(ErrorKind::InvalidData, "Parsing failed");
+
+    for line in meminfo.lines() {
+        let mut kv = line.splitn(2, ':');
+
+        if kv.next() == Some("MemTotal") {
+            let mut value = kv.next().ok_or_else(err)?.split_whitespace();
+            let kibibytes: u64 =
+                value.next().ok_or_else(err)?.parse().map_err(|_| err())?;
+
+            return match value.next() {
+                Some("kB") => Ok(kibibytes * 1024),
+                None => Ok(kibibytes),
+                Some(_) => Err(err()),
+            };
+        }
+    }
+
+    Err(super::err_missing_record())
+}
+
+/// Get the first value of a field in `/proc/cpuinfo`.
+#[cfg(target_os = "linux")]
+fn cpuinfo_field(field: &str) -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+
+    cpuinfo.lines().find_map(|line| {
+        let mut kv = line.splitn(2, ':');
+
+        if kv.next()?.trim() == field {
+            Some(kv.next()?.trim().to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Get the 32-bit ARM architecture version from the "CPU architecture" field of
+/// `/proc/cpuinfo`.
+#[cfg(target_os = "linux")]
+fn arm_cpuinfo() -> Option<Arch> {
+    let version = cpuinfo_field("CPU architecture")?;
+
+    // Example: "5TEJ", "7" or "8" (an ARMv8 CPU running 32-bit code)
+    Some(match version.get(..1)? {
+        "5" => Arch::ArmV5,
+        "6" => Arch::ArmV6,
+        "7" | "8" => Arch::ArmV7,
+        _ => return None,
+    })
+}
+
+/// Detect the C library from the dynamic loaders installed in `/lib` and
+/// `/lib64`, falling back to the C library this program is linked to.
+#[cfg(target_os = "linux")]
+fn libc_kind() -> LibcKind {
+    extern "C" {
+        fn confstr(name: c_int, buf: *mut c_char, len: usize) -> usize;
+    }
+
+    const CS_GNU_LIBC_VERSION: c_int = 2;
+
+    let loader = |prefixes: &[&str]| {
+        ["/lib", "/lib64"].iter().any(|dir| {
+            fs::read_dir(dir)
+                .map(|dir| {
+                    dir.flatten().any(|entry| {
+                        let name = entry.file_name();
+                        let name = name.to_string_lossy();
+
+                        prefixes.iter().any(|prefix| name.starts_with(prefix))
+                    })
+                })
+                .unwrap_or(false)
+        })
+    };
+
+    // Example: "ld-musl-x86_64.so.1"
+    if loader(&["ld-musl-"]) {
+        return LibcKind::Musl;
+    }
+
+    // Example: "ld-linux-x86-64.so.2", or "ld64.so.2" on 64-bit PowerPC
+    if loader(&["ld-linux", "ld64.so"]) {
+        return LibcKind::Glibc;
+    }
+
+    // Only glibc has a version string (musl fails with `EINVAL`)
+    if unsafe { confstr(CS_GNU_LIBC_VERSION, ptr::null_mut(), 0) } != 0 {
+        return LibcKind::Glibc;
+    }
+
+    LibcKind::Unknown
+}
+
+/// Get the hostname configured in `/etc/hostname`, skipping comments.
+#[cfg(target_os = "linux")]
+fn configured_hostname() -> Result<String> {
+    let hostname = fs::read_to_string("/etc/hostname")?;
+
+    hostname
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToString::to_string)
+        .ok_or_else(super::err_empty_record)
+}
+
+/// Get the Kerberos default realm, if the machine is joined to a domain (such
+/// as with `realm join`, which creates the host keytab).
+#[cfg(target_os = "linux")]
+fn kerberos_realm() -> Option<String> {
+    if !Path::new("/etc/krb5.keytab").exists() {
+        return None;
+    }
+
+    let config = fs::read_to_string("/etc/krb5.conf").ok()?;
+    let mut libdefaults = false;
+
+    for line in config.lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            libdefaults = line == "[libdefaults]";
+            continue;
+        }
+
+        if !libdefaults || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        // Example: "default_realm = EXAMPLE.COM"
+        let mut pair = line.splitn(2, '=');
+
+        if pair.next().map(str::trim) == Some("default_realm") {
+            let realm = pair.next()?.trim();
+
+            if realm.is_empty() {
+                return None;
+            }
+
+            return Some(realm.to_string());
+        }
+    }
+
+    None
+}
+
+/// Detect the init system from the name of process 1, and well-known paths.
+#[cfg(target_os = "linux")]
+fn init_system() -> Result<String> {
+    let path = |path: &str| fs::metadata(path).is_ok();
+
+    if path("/run/systemd/system") {
+        return Ok("systemd".to_string());
+    }
+
+    let comm = fs::read_to_string("/proc/1/comm").unwrap_or_default();
+    let init = match comm.trim() {
+        "systemd" => "systemd",
+        "openrc-init" => "openrc",
+        "runit" | "runit-init" => "runit",
+        "s6-svscan" => "s6",
+        "dinit" => "dinit",
+        "init" | "" if path("/sbin/openrc") => "openrc",
+        "init" | "" if path("/etc/runit") => "runit",
+        "init" | "" if path("/etc/init.d") || path("/etc/inittab") => {
+            "sysvinit"
+        }
+        // Unknown init systems, and ordinary programs running as process 1
+        // (as in containers)
+        _ => return Err(super::err_missing_record()),
+    };
+
+    Ok(init.to_string())
+}
 
-            if ret != 0 {
-                return Err(Error::last_os_error());
-            }
+/// Detect the audio server from running processes, the PulseAudio environment
+/// variable and sockets, falling back to ALSA when a sound card exists.
+#[cfg(target_os = "linux")]
+fn audio_server() -> Option<String> {
+    let runtime_dir = env::var_os("XDG_RUNTIME_DIR")
+        .map(Into::into)
+        .unwrap_or_else(|| {
+            Path::new("/run/user").join(unsafe { geteuid() }.to_string())
+        });
+    let server = find_user_proc(|comm| match comm {
+        "pipewire" => Some("PipeWire"),
+        "pulseaudio" => Some("PulseAudio"),
+        _ => None,
+    });
+    let server = if let Some(server) = server {
+        server
+    } else if runtime_dir.join("pipewire-0").exists() {
+        "PipeWire"
+    } else if env::var_os("PULSE_SERVER").is_some()
+        || runtime_dir.join("pulse").exists()
+    {
+        "PulseAudio"
+    } else if Path::new("/dev/snd").exists() {
+        "ALSA"
+    } else {
+        return None;
+    };
 
-            let _passwd = _passwd.assume_init();
+    Some(server.to_string())
+}
 
-            if _passwd.is_null() {
-                return Err(super::err_null_record());
-            }
-            passwd.assume_init()
+/// Detect the hypervisor from the DMI system information, `/sys/hypervisor`,
+/// and the CPUID hypervisor leaf.
+#[cfg(target_os = "linux")]
+fn hypervisor() -> Result<Option<String>> {
+    let dmi = |field| {
+        fs::read_to_string(format!("/sys/class/dmi/id/{}", field))
+            .map(|value| value.trim().to_string())
+    };
+    let vendor = dmi("sys_vendor");
+    let product = dmi("product_name");
+
+    if let (Ok(vendor), Ok(product)) = (&vendor, &product) {
+        let name = match (vendor.as_str(), product.as_str()) {
+            (_, "KVM") | ("Red Hat", _) => Some("KVM"),
+            ("QEMU", _) => Some("QEMU"),
+            ("VMware, Inc.", _) => Some("VMware"),
+            ("Microsoft Corporation", "Virtual Machine") => Some("Hyper-V"),
+            ("Xen", _) => Some("Xen"),
+            ("innotek GmbH", _) | (_, "VirtualBox") => Some("VirtualBox"),
+            ("Parallels Software International Inc.", _) => Some("Parallels"),
+            (_, "BHYVE") => Some("bhyve"),
+            _ => None,
+        };
+
+        if let Some(name) = name {
+            return Ok(Some(name.to_string()));
         }
+    }
 
-        #[cfg(target_os = "illumos")]
-        {
-            let ret = getpwuid_r(
-                geteuid(),
-                passwd.as_mut_ptr(),
-                buffer.as_mut_ptr() as *mut c_void,
-                BUF_SIZE.try_into().unwrap_or(c_int::MAX),
-            );
+    if let Ok(kind) = fs::read_to_string("/sys/hypervisor/type") {
+        if kind.trim() == "xen" {
+            return Ok(Some("Xen".to_string()));
+        }
+    }
 
-            if ret.is_null() {
-                return Err(Error::last_os_error());
-            }
-            passwd.assume_init()
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        Ok(super::cpuid_hypervisor())
+    }
+
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    {
+        if let (Err(error), Err(_)) = (vendor, product) {
+            return Err(error);
         }
+
+        Ok(None)
+    }
+}
+
+/// Read the `hw.physmem64` sysctl.
+#[cfg(target_os = "openbsd")]
+fn memory_total() -> Result<u64> {
+    const CTL_HW: c_int = 6;
+    const HW_PHYSMEM64: c_int = 19;
+
+    let mib = [CTL_HW, HW_PHYSMEM64];
+    let mut buf = [0u8; 8];
+    let mut len = buf.len();
+    let ret = unsafe {
+        sysctl(
+            mib.as_ptr(),
+            2,
+            buf.as_mut_ptr().cast(),
+            &mut len,
+            ptr::null_mut(),
+            0,
+        )
     };
 
-    // Extract names.
-    if let Name::Real = name {
-        os_from_cstring_gecos(passwd.pw_gecos)
-    } else {
-        os_from_cstring(passwd.pw_name)
+    if ret == -1 {
+        return Err(Error::last_os_error());
     }
+
+    Ok(u64::from_ne_bytes(buf))
 }
 
-#[cfg(target_os = "macos")]
+/// Count the unique (physical package, core) pairs in `/proc/cpuinfo`.
+#[cfg(target_os = "linux")]
+fn cpu_cores_physical() -> Result<usize> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo")?;
+    let mut cores = Vec::new();
+    let mut physical_id = None;
+
+    for line in cpuinfo.lines() {
+        let mut kv = line.splitn(2, ':');
+        let key = kv.next().unwrap_or_default().trim();
+        let value = kv.next().map(str::trim);
+
+        match key {
+            "physical id" => physical_id = value,
+            "core id" => {
+                let core = (physical_id, value);
+
+                if !cores.contains(&core) {
+                    cores.push(core);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if cores.is_empty() {
+        return Err(super::err_missing_record());
+    }
+
+    Ok(cores.len())
+}
+
+#[cfg(any(target_os = "ios", target_os = "macos"))]
 fn distro_xml(data: String) -> Result<String> {
     let mut product_name = None;
     let mut user_visible_version = None;
@@ -356,6 +1714,19 @@ fn distro_xml(data: String) -> Result<String> {
 }
 
 #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+fn os_release() -> Result<String> {
+    super::os_release(Path::new("/etc/os-release"))
+}
+
+#[cfg(any(
+    target_os = "ios",
     target_os = "macos",
     target_os = "freebsd",
     target_os = "netbsd",
@@ -413,6 +1784,7 @@ unsafe fn uname(buf: *mut UtsName) -> c_int {
     extern "C" {
         #[cfg(any(
             target_os = "linux",
+            target_os = "ios",
             target_os = "macos",
             target_os = "dragonfly",
             target_os = "netbsd",
@@ -435,24 +1807,140 @@ unsafe fn uname(buf: *mut UtsName) -> c_int {
     uname(buf)
 }
 
-impl Target for Os {
-    fn langs(self) -> Result<String> {
+/// Get the OS name and release from `uname()` (example: "FreeBSD
+/// 14.0-RELEASE").
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn uname_distro() -> Result<String> {
+    let mut buf = UtsName::default();
+
+    if unsafe { uname(&mut buf) } == -1 {
+        return Err(Error::last_os_error());
+    }
+
+    let sysname = unsafe { CStr::from_ptr(buf.sysname.as_ptr()) };
+    let release = unsafe { CStr::from_ptr(buf.release.as_ptr()) };
+
+    Ok(format!(
+        "{} {}",
+        sysname.to_string_lossy(),
+        release.to_string_lossy(),
+    ))
+}
+
+impl Backend for Os {
+    #[cfg(any(target_os = "ios", target_os = "macos"))]
+    fn measurement_system(self) -> Result<MeasurementSystem> {
+        let value = apple_locale_value(unsafe { kCFLocaleMeasurementSystem })
+            .ok_or_else(super::err_missing_record)?;
+
+        match value.as_str() {
+            "Metric" => Ok(MeasurementSystem::Metric),
+            "U.S." => Ok(MeasurementSystem::Uscs),
+            "U.K." => Ok(MeasurementSystem::Imperial),
+            _ => Err(Error::new(ErrorKind::InvalidData, "Parsing failed")),
+        }
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "illumos",
+    ))]
+    fn measurement_system(self) -> Result<MeasurementSystem> {
+        measurement_system()
+    }
+
+    #[cfg(any(target_os = "ios", target_os = "macos"))]
+    fn decimal_separator(self) -> Result<char> {
+        apple_locale_value(unsafe { kCFLocaleDecimalSeparator })
+            .and_then(|separator| separator.chars().next())
+            .ok_or_else(super::err_missing_record)
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "illumos",
+    ))]
+    fn decimal_separator(self) -> Result<char> {
+        decimal_separator()
+    }
+
+    fn langs_raw(self) -> Result<Vec<String>> {
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        {
+            let langs = apple_langs();
+
+            if !langs.is_empty() {
+                return Ok(langs);
+            }
+        }
+
         super::unix_lang()
     }
 
     fn realname(self) -> Result<OsString> {
-        getpwuid(Name::Real)
+        getpwuid(None, Name::Real)
     }
 
     fn username(self) -> Result<OsString> {
-        getpwuid(Name::User)
+        getpwuid(None, Name::User)
+            .or_else(|error| username_from_env().ok_or(error))
+    }
+
+    fn username_into(self, buf: &mut String) -> Result<()> {
+        with_passwd(None, |passwd| push_cstring(buf, passwd.pw_name)).or_else(
+            |error| {
+                // Only a failed lookup falls back to the environment, not a
+                // username that isn't valid UTF-8
+                if error.kind() == ErrorKind::InvalidData {
+                    return Err(error);
+                }
+
+                let username = username_from_env().ok_or(error)?;
+
+                buf.push_str(&conversions::string_from_os(username)?);
+
+                Ok(())
+            },
+        )
+    }
+
+    fn passwd(self) -> Result<Passwd> {
+        passwd()
+    }
+
+    fn primary_group(self) -> Result<OsString> {
+        getpwuid(None, Name::Group)
+    }
+
+    fn gecos(self) -> Result<OsString> {
+        getpwuid(None, Name::Gecos)
+    }
+
+    fn realname_of(self, username: &str) -> Result<OsString> {
+        getpwuid(Some(&cstring_from_username(username)?), Name::Real)
+    }
+
+    fn home_dir_of(self, username: &str) -> Result<OsString> {
+        getpwuid(Some(&cstring_from_username(username)?), Name::Home)
     }
 
     fn devicename(self) -> Result<OsString> {
         #[cfg(target_os = "macos")]
         {
+            // `os_from_cfstring()` releases the copied string
             let out = os_from_cfstring(unsafe {
-                SCDynamicStoreCopyComputerName(null_mut(), null_mut())
+                SCDynamicStoreCopyComputerName(dynamic_store(), ptr::null_mut())
             });
 
             if out.as_bytes().is_empty() {
@@ -462,6 +1950,11 @@ impl Target for Os {
             Ok(out)
         }
 
+        #[cfg(target_os = "ios")]
+        {
+            self.hostname().map(OsString::from)
+        }
+
         #[cfg(target_os = "illumos")]
         {
             let mut nodename = fs::read("/etc/nodename")?;
@@ -486,6 +1979,21 @@ impl Target for Os {
             target_os = "openbsd",
         ))]
         {
+            // Set with `hostnamectl`, and readable from sandboxes
+            #[cfg(all(target_os = "linux", feature = "dbus"))]
+            {
+                if let Ok(name) = super::dbus::system_property(
+                    "org.freedesktop.hostname1",
+                    "/org/freedesktop/hostname1",
+                    "org.freedesktop.hostname1",
+                    "PrettyHostname",
+                ) {
+                    if !name.is_empty() {
+                        return Ok(name.into());
+                    }
+                }
+            }
+
             let machine_info = fs::read("/etc/machine-info")?;
 
             for i in machine_info.split(|b| *b == b'\n') {
@@ -504,11 +2012,11 @@ impl Target for Os {
     }
 
     fn hostname(self) -> Result<String> {
-        // Maximum hostname length = 255, plus a NULL byte.
-        let mut string = Vec::<u8>::with_capacity(256);
+        // Maximum hostname length, plus a NULL byte.
+        let mut string = Vec::<u8>::with_capacity(HOSTNAME_MAX_LEN + 1);
 
         unsafe {
-            if gethostname(string.as_mut_ptr().cast(), 255) == -1 {
+            if gethostname(string.as_mut_ptr().cast(), HOSTNAME_MAX_LEN) == -1 {
                 return Err(Error::last_os_error());
             }
 
@@ -520,8 +2028,91 @@ impl Target for Os {
         })
     }
 
+    fn hostname_into(self, buf: &mut String) -> Result<()> {
+        // Maximum hostname length, plus a NULL byte.
+        let mut string: [c_char; HOSTNAME_MAX_LEN + 1] =
+            [0; HOSTNAME_MAX_LEN + 1];
+
+        if unsafe { gethostname(string.as_mut_ptr().cast(), HOSTNAME_MAX_LEN) }
+            == -1
+        {
+            return Err(Error::last_os_error());
+        }
+
+        let string = unsafe { CStr::from_ptr(string.as_ptr()) };
+
+        buf.push_str(string.to_str().map_err(|_| {
+            Error::new(ErrorKind::InvalidData, "Hostname not valid UTF-8")
+        })?);
+
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    fn configured_hostname(self) -> Result<String> {
+        configured_hostname()
+    }
+
+    fn nodename(self) -> Result<String> {
+        let mut buf = UtsName::default();
+
+        if unsafe { uname(&mut buf) } == -1 {
+            return Err(Error::last_os_error());
+        }
+
+        let nodename = unsafe { CStr::from_ptr(buf.nodename.as_ptr()) };
+
+        if nodename.to_bytes().is_empty() {
+            return Err(super::err_empty_record());
+        }
+
+        nodename.to_str().map(ToString::to_string).map_err(|_| {
+            Error::new(ErrorKind::InvalidData, "Nodename not valid UTF-8")
+        })
+    }
+
+    fn fqdn(self) -> Result<String> {
+        let mut hostname = self.hostname()?.into_bytes();
+        let mut hints: AddrInfo = unsafe { mem::zeroed() };
+        let mut res = mem::MaybeUninit::<*mut AddrInfo>::uninit();
+
+        hostname.push(b'\0');
+        hints.ai_flags = AI_CANONNAME;
+
+        unsafe {
+            if getaddrinfo(
+                hostname.as_ptr().cast(),
+                ptr::null(),
+                &hints,
+                res.as_mut_ptr(),
+            ) != 0
+            {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    "Failed to resolve hostname",
+                ));
+            }
+
+            let res = res.assume_init();
+            let canonname = (*res).ai_canonname;
+            let fqdn = if canonname.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(canonname).to_bytes().to_vec())
+            };
+
+            freeaddrinfo(res);
+
+            let fqdn = fqdn.ok_or_else(super::err_empty_record)?;
+
+            String::from_utf8(fqdn).map_err(|_| {
+                Error::new(ErrorKind::InvalidData, "FQDN not valid UTF-8")
+            })
+        }
+    }
+
     fn distro(self) -> Result<String> {
-        #[cfg(target_os = "macos")]
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
         {
             if let Ok(data) = fs::read_to_string(
                 "/System/Library/CoreServices/ServerVersion.plist",
@@ -545,52 +2136,86 @@ impl Target for Os {
             target_os = "illumos",
         ))]
         {
-            let program = fs::read("/etc/os-release")?;
-            let distro = String::from_utf8_lossy(&program);
-            let err = || Error::new(ErrorKind::InvalidData, "Parsing failed");
-            let mut fallback = None;
-
-            for i in distro.split('\n') {
-                let mut j = i.split('=');
-
-                match j.next().ok_or_else(err)? {
-                    "PRETTY_NAME" => {
-                        return Ok(j
-                            .next()
-                            .ok_or_else(err)?
-                            .trim_matches('"')
-                            .to_string());
-                    }
-                    "NAME" => {
-                        fallback = Some(
-                            j.next()
-                                .ok_or_else(err)?
-                                .trim_matches('"')
-                                .to_string(),
-                        )
-                    }
-                    _ => {}
-                }
-            }
-
-            fallback.ok_or_else(err)
+            let os_release = match os_release() {
+                Ok(os_release) => os_release,
+                // BSDs don't always have an os-release file
+                #[cfg(any(
+                    target_os = "dragonfly",
+                    target_os = "freebsd",
+                    target_os = "netbsd",
+                    target_os = "openbsd",
+                ))]
+                Err(_) => return uname_distro(),
+                #[cfg(any(target_os = "linux", target_os = "illumos"))]
+                Err(e) => return Err(e),
+            };
+
+            super::os_release_distro(&os_release)
         }
     }
 
-    fn desktop_env(self) -> DesktopEnv {
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "illumos",
+    ))]
+    fn distro_id(self) -> Result<String> {
+        let os_release = os_release()?;
+
+        super::os_release_field(&os_release, "ID")
+            .map(ToString::to_string)
+            .ok_or_else(super::err_missing_record)
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "illumos",
+    ))]
+    fn distro_id_like(self) -> Result<Vec<String>> {
+        let os_release = os_release()?;
+
+        // Distributions that aren't derived from another leave out `ID_LIKE`
+        Ok(super::os_release_field(&os_release, "ID_LIKE")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect())
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "illumos",
+    ))]
+    fn distro_codename(self) -> Result<Option<String>> {
+        let os_release = os_release()?;
+
+        Ok(super::os_release_field(&os_release, "VERSION_CODENAME")
+            .filter(|codename| !codename.is_empty())
+            .map(ToString::to_string))
+    }
+
+    fn desktop_env(self) -> Result<DesktopEnv> {
         #[cfg(target_os = "macos")]
-        let env = "Aqua";
+        {
+            Ok(DesktopEnv::Aqua)
+        }
+
+        #[cfg(target_os = "ios")]
+        {
+            Ok(DesktopEnv::Ios)
+        }
 
-        // FIXME: WhoAmI 2.0: use `let else`
-        #[cfg(any(
-            target_os = "linux",
-            target_os = "dragonfly",
-            target_os = "freebsd",
-            target_os = "netbsd",
-            target_os = "openbsd",
-            target_os = "illumos",
-        ))]
-        let env = env::var_os("DESKTOP_SESSION");
         #[cfg(any(
             target_os = "linux",
             target_os = "dragonfly",
@@ -599,60 +2224,226 @@ impl Target for Os {
             target_os = "openbsd",
             target_os = "illumos",
         ))]
-        let env = if let Some(ref env) = env {
-            env.to_string_lossy()
-        } else {
-            return DesktopEnv::Unknown("Unknown".to_string());
-        };
-
-        if env.eq_ignore_ascii_case("AQUA") {
-            DesktopEnv::Aqua
-        } else if env.eq_ignore_ascii_case("GNOME") {
-            DesktopEnv::Gnome
-        } else if env.eq_ignore_ascii_case("LXDE") {
-            DesktopEnv::Lxde
-        } else if env.eq_ignore_ascii_case("OPENBOX") {
-            DesktopEnv::Openbox
-        } else if env.eq_ignore_ascii_case("I3") {
-            DesktopEnv::I3
-        } else if env.eq_ignore_ascii_case("UBUNTU") {
-            DesktopEnv::Ubuntu
-        } else if env.eq_ignore_ascii_case("PLASMA5") {
-            DesktopEnv::Kde
-        // TODO: Other Linux Desktop Environments
-        } else {
-            DesktopEnv::Unknown(env.to_string())
+        {
+            let var = |name: &str| {
+                env::var_os(name).map(|v| v.to_string_lossy().into_owned())
+            };
+
+            if let Some(env) = desktop_env_from_vars(var) {
+                Ok(env)
+            } else if let Some(env) = desktop_env_from_ancestors() {
+                Ok(env)
+            } else if let Some(env) = desktop_env_from_procs() {
+                Ok(env)
+            } else if !graphical_session() {
+                Ok(DesktopEnv::Headless)
+            } else {
+                Err(Error::new(
+                    ErrorKind::NotFound,
+                    "Couldn't detect desktop environment",
+                ))
+            }
         }
     }
 
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "illumos",
+    ))]
+    fn desktop_env_version(self) -> Result<String> {
+        desktop_env_version(self.desktop_env()?)
+    }
+
     #[inline(always)]
-    fn platform(self) -> Platform {
-        #[cfg(target_os = "linux")]
-        {
-            Platform::Linux
+    fn platform(self) -> Result<Platform> {
+        Ok(Platform::current())
+    }
+
+    fn cpu_cores(self) -> Result<usize> {
+        let cores = unsafe { sysconf(SC_NPROCESSORS_ONLN) };
+
+        if cores == -1 {
+            return Err(Error::last_os_error());
         }
 
-        #[cfg(target_os = "macos")]
-        {
-            Platform::MacOS
+        Ok(cores as usize)
+    }
+
+    #[cfg(target_os = "ios")]
+    fn device_model(self) -> Result<OsString> {
+        let mut buf = UtsName::default();
+
+        if unsafe { uname(&mut buf) } == -1 {
+            return Err(Error::last_os_error());
         }
 
-        #[cfg(any(
-            target_os = "dragonfly",
-            target_os = "freebsd",
-            target_os = "netbsd",
-            target_os = "openbsd",
-        ))]
-        {
-            Platform::Bsd
+        let model = unsafe { CStr::from_ptr(buf.machine.as_ptr()) };
+
+        if model.to_bytes().is_empty() {
+            return Err(super::err_empty_record());
         }
 
-        #[cfg(target_os = "illumos")]
-        {
-            Platform::Illumos
+        Ok(OsString::from_vec(model.to_bytes().to_vec()))
+    }
+
+    #[cfg(target_os = "macos")]
+    fn device_model(self) -> Result<OsString> {
+        sysctl_os_string(b"hw.model\0")
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "openbsd"))]
+    fn memory_total(self) -> Result<u64> {
+        memory_total()
+    }
+
+    #[cfg(any(target_os = "ios", target_os = "macos"))]
+    fn memory_total(self) -> Result<u64> {
+        sysctl_u64(b"hw.memsize\0")
+    }
+
+    #[cfg(any(target_os = "dragonfly", target_os = "freebsd"))]
+    fn memory_total(self) -> Result<u64> {
+        sysctl_u64(b"hw.physmem\0")
+    }
+
+    #[cfg(target_os = "netbsd")]
+    fn memory_total(self) -> Result<u64> {
+        sysctl_u64(b"hw.physmem64\0")
+    }
+
+    #[cfg(target_os = "illumos")]
+    fn memory_total(self) -> Result<u64> {
+        let (page_size, pages) =
+            unsafe { (sysconf(SC_PAGESIZE), sysconf(SC_PHYS_PAGES)) };
+
+        if page_size == -1 || pages == -1 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(page_size as u64 * pages as u64)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn cpu_cores_physical(self) -> Result<usize> {
+        cpu_cores_physical()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn libc_kind(self) -> Result<LibcKind> {
+        Ok(libc_kind())
+    }
+
+    #[cfg(target_os = "macos")]
+    fn arch_native(self) -> Result<Arch> {
+        // Set to 1 for x86_64 processes translated by Rosetta 2
+        if let Ok(1) = sysctl_u64(b"sysctl.proc_translated\0") {
+            return Ok(Arch::Arm64);
+        }
+
+        self.arch()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn arch_hardware(self) -> Result<Arch> {
+        Ok(match self.arch()? {
+            // A 32-bit kernel on an ARMv8 CPU
+            Arch::ArmV7
+                if cpuinfo_field("CPU architecture")
+                    .map_or(false, |version| version.starts_with('8')) =>
+            {
+                Arch::Arm64
+            }
+            // An x32 process always runs on a 64-bit CPU
+            Arch::X32 => Arch::X64,
+            // A 32-bit kernel on a CPU with long mode
+            Arch::I386 | Arch::I586 | Arch::I686
+                if cpuinfo_field("flags").map_or(false, |flags| {
+                    flags.split_whitespace().any(|flag| flag == "lm")
+                }) =>
+            {
+                Arch::X64
+            }
+            arch => arch,
+        })
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+    ))]
+    fn boot_time(self) -> Result<SystemTime> {
+        boot_time()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn sandboxed(self) -> bool {
+        sandboxed()
+    }
+
+    fn tty(self) -> bool {
+        // Standard output
+        unsafe { isatty(1) == 1 }
+    }
+
+    #[cfg(not(target_os = "ios"))]
+    fn display_scale(self) -> Result<f32> {
+        display_scale()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn account(self) -> Result<OsString> {
+        let mut account = self.username()?;
+
+        // SSSD may already qualify the username with the domain
+        if account.as_bytes().contains(&b'@') {
+            return Ok(account);
         }
+
+        if let Some(realm) = kerberos_realm() {
+            account.push("@");
+            account.push(realm);
+        }
+
+        Ok(account)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn init_system(self) -> Result<String> {
+        init_system()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn hypervisor(self) -> Result<Option<String>> {
+        hypervisor()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn audio_server(self) -> Result<Option<String>> {
+        Ok(audio_server())
+    }
+
+    #[cfg(any(target_os = "ios", target_os = "macos"))]
+    fn cpu_cores_physical(self) -> Result<usize> {
+        Ok(sysctl_u64(b"hw.physicalcpu\0")? as usize)
+    }
+
+    // On iOS, the uname machine field is the device model rather than the CPU
+    // architecture.
+    #[cfg(target_os = "ios")]
+    #[inline(always)]
+    fn arch(self) -> Result<Arch> {
+        Ok(Arch::current())
     }
 
+    #[cfg(not(target_os = "ios"))]
     #[inline(always)]
     fn arch(self) -> Result<Arch> {
         let mut buf = UtsName::default();
@@ -665,29 +2456,18 @@ impl Target for Os {
             unsafe { CStr::from_ptr(buf.machine.as_ptr()) }.to_string_lossy();
 
         Ok(match arch_str.as_ref() {
-            "aarch64" | "arm64" | "aarch64_be" | "armv8b" | "armv8l" => {
-                Arch::Arm64
+            #[cfg(target_os = "linux")]
+            "arm" => arm_cpuinfo().unwrap_or(Arch::ArmV6),
+            // The kernel doesn't distinguish x32 processes
+            "x86_64"
+                if cfg!(all(
+                    target_arch = "x86_64",
+                    target_pointer_width = "32",
+                )) =>
+            {
+                Arch::X32
             }
-            "armv5" => Arch::ArmV5,
-            "armv6" | "arm" => Arch::ArmV6,
-            "armv7" => Arch::ArmV7,
-            "i386" => Arch::I386,
-            "i586" => Arch::I586,
-            "i686" | "i686-AT386" => Arch::I686,
-            "mips" => Arch::Mips,
-            "mipsel" => Arch::MipsEl,
-            "mips64" => Arch::Mips64,
-            "mips64el" => Arch::Mips64El,
-            "powerpc" | "ppc" | "ppcle" => Arch::PowerPc,
-            "powerpc64" | "ppc64" | "ppc64le" => Arch::PowerPc64,
-            "powerpc64le" => Arch::PowerPc64Le,
-            "riscv32" => Arch::Riscv32,
-            "riscv64" => Arch::Riscv64,
-            "s390x" => Arch::S390x,
-            "sparc" => Arch::Sparc,
-            "sparc64" => Arch::Sparc64,
-            "x86_64" | "amd64" => Arch::X64,
-            _ => Arch::Unknown(arch_str.into_owned()),
+            machine => Arch::from_machine(machine),
         })
     }
 }
@@ -4,12 +4,12 @@ compile_error!("Unexpected pointer width for target platform");
 use std::{env, ffi::OsString};
 
 use crate::{
-    os::{Os, Target},
+    os::{Backend, Os},
     Arch, DesktopEnv, Platform, Result,
 };
 
-impl Target for Os {
-    fn langs(self) -> Result<String> {
+impl Backend for Os {
+    fn langs_raw(self) -> Result<Vec<String>> {
         super::unix_lang()
     }
 
@@ -45,25 +45,21 @@ impl Target for Os {
     }
 
     #[inline(always)]
-    fn desktop_env(self) -> DesktopEnv {
-        if let Some(ref env) = env::var_os("DESKTOP_SESSION") {
+    fn desktop_env(self) -> Result<DesktopEnv> {
+        Ok(if let Some(ref env) = env::var_os("DESKTOP_SESSION") {
             DesktopEnv::Unknown(env.to_string_lossy().to_string())
         } else {
             DesktopEnv::Unknown("Unknown WASI".to_string())
-        }
+        })
     }
 
     #[inline(always)]
-    fn platform(self) -> Platform {
-        Platform::Unknown("WASI".to_string())
+    fn platform(self) -> Result<Platform> {
+        Ok(Platform::current())
     }
 
     #[inline(always)]
     fn arch(self) -> Result<Arch> {
-        Ok(if cfg!(target_pointer_width = "64") {
-            Arch::Wasm64
-        } else {
-            Arch::Wasm32
-        })
+        Ok(Arch::current())
     }
 }
@@ -6,10 +6,11 @@ use std::{
     io::{Error, ErrorKind},
 };
 
+use js_sys::{Array, JsString, Object, Reflect};
 use web_sys::window;
 
 use crate::{
-    os::{Os, Target},
+    os::{Backend, Os},
     Arch, DesktopEnv, Platform, Result,
 };
 
@@ -23,8 +24,95 @@ fn document_domain() -> Option<String> {
     window()?.document()?.location()?.hostname().ok()
 }
 
-impl Target for Os {
-    fn langs(self) -> Result<String> {
+// Check the user agent's platform for an iPhone, iPad or iPod
+fn is_ios(platform: &str) -> bool {
+    platform.contains("iPhone")
+        || platform.contains("iPad")
+        || platform.contains("iPod")
+}
+
+// Get the name of the BSD from the user agent's platform
+fn bsd(platform: &str) -> Option<&'static str> {
+    if platform.contains("FreeBSD") {
+        Some("FreeBSD")
+    } else if platform.contains("OpenBSD") {
+        Some("OpenBSD")
+    } else if platform.contains("NetBSD") {
+        Some("NetBSD")
+    } else {
+        None
+    }
+}
+
+// Get a string property of a JavaScript object
+fn property(object: &Object, name: &str) -> Option<String> {
+    Reflect::get(object, &JsString::from(name))
+        .ok()?
+        .as_string()
+}
+
+// Get the browser's name and major version from `navigator.userAgentData`
+// (not available in every browser)
+fn user_agent_data_browser() -> Option<(String, String)> {
+    let navigator = window()?.navigator();
+    let data =
+        Reflect::get(&navigator, &JsString::from("userAgentData")).ok()?;
+    let brands = Reflect::get(&data, &JsString::from("brands")).ok()?;
+
+    if !Array::is_array(&brands) {
+        return None;
+    }
+
+    // Skip the "GREASE" brands (example: "Not A(Brand"), and prefer a specific
+    // Chromium-based browser over "Chromium" itself
+    let brands = Array::from(&brands)
+        .iter()
+        .map(Object::from)
+        .filter_map(|brand| {
+            Some((property(&brand, "brand")?, property(&brand, "version")?))
+        })
+        .filter(|(brand, _)| !brand.starts_with("Not"))
+        .collect::<Vec<_>>();
+    let (brand, version) = brands
+        .iter()
+        .find(|(brand, _)| brand != "Chromium")
+        .or_else(|| brands.first())?;
+    let brand = brand
+        .trim_start_matches("Google ")
+        .trim_start_matches("Microsoft ");
+
+    Some((brand.to_string(), version.clone()))
+}
+
+// Get the browser's name and major version from the user agent
+fn user_agent_browser() -> Option<(String, String)> {
+    let user_agent = user_agent()?;
+    // Order matters, since most browsers claim to be others as well
+    let (name, token) = [
+        ("Edge", "Edg/"),
+        ("Edge", "EdgA/"),
+        ("Edge", "EdgiOS/"),
+        ("Opera", "OPR/"),
+        ("Samsung Internet", "SamsungBrowser/"),
+        ("Firefox", "Firefox/"),
+        ("Firefox", "FxiOS/"),
+        ("Chrome", "CriOS/"),
+        ("Chrome", "Chrome/"),
+        ("Safari", "Version/"),
+    ]
+    .iter()
+    .find(|(_, token)| user_agent.contains(token))?;
+    let begin = user_agent.find(token)? + token.len();
+    let version = user_agent[begin..]
+        .split(|c: char| !c.is_ascii_digit())
+        .next()
+        .unwrap_or_default();
+
+    Some((name.to_string(), version.to_string()))
+}
+
+impl Backend for Os {
+    fn langs_raw(self) -> Result<Vec<String>> {
         if let Some(window) = window() {
             Ok(window
                 .navigator()
@@ -32,8 +120,7 @@ impl Target for Os {
                 .to_vec()
                 .into_iter()
                 .filter_map(|l| l.as_string())
-                .collect::<Vec<String>>()
-                .join(";"))
+                .collect())
         } else {
             Err(Error::new(ErrorKind::NotFound, "Window missing"))
         }
@@ -102,7 +189,29 @@ impl Target for Os {
         let end = string.find(')').ok_or_else(err)?;
         let string = &string[begin + 1..end];
 
-        Ok(if string.contains("Win32") || string.contains("Win64") {
+        Ok(if let Some(begin) = string.find("Android") {
+            // Example: "Linux; Android 14; Pixel 8"
+            let string = &string[begin..];
+
+            string[..string.find(';').unwrap_or(string.len())].to_string()
+        } else if is_ios(string) {
+            // Example: "iPhone; CPU iPhone OS 17_1 like Mac OS X"
+            if let Some(begin) = string.find(" OS ") {
+                let version = string[begin + 4..]
+                    .split(' ')
+                    .next()
+                    .unwrap_or_default()
+                    .replace('_', ".");
+
+                format!("iOS {}", version)
+            } else {
+                "iOS".to_string()
+            }
+        } else if string.contains("CrOS") {
+            "ChromeOS".to_string()
+        } else if let Some(bsd) = bsd(string) {
+            bsd.to_string()
+        } else if string.contains("Win32") || string.contains("Win64") {
             let begin = if let Some(b) = string.find("NT") {
                 b
             } else {
@@ -147,9 +256,6 @@ impl Target for Os {
             }
         } else {
             // TODO:
-            // Platform::FreeBsd,
-            // Platform::Ios,
-            // Platform::Android,
             // Platform::Nintendo,
             // Platform::Xbox,
             // Platform::PlayStation,
@@ -160,36 +266,44 @@ impl Target for Os {
         })
     }
 
+    fn browser(self) -> Result<(String, String)> {
+        user_agent_data_browser()
+            .or_else(user_agent_browser)
+            .ok_or_else(super::err_missing_record)
+    }
+
     #[inline(always)]
-    fn desktop_env(self) -> DesktopEnv {
-        DesktopEnv::WebBrowser
+    fn desktop_env(self) -> Result<DesktopEnv> {
+        Ok(DesktopEnv::WebBrowser)
     }
 
-    fn platform(self) -> Platform {
+    fn platform(self) -> Result<Platform> {
         let string = user_agent().unwrap_or_default();
-        let begin = if let Some(b) = string.find('(') {
-            b
-        } else {
-            return Platform::Unknown("Unknown".to_string());
-        };
-        let end = if let Some(e) = string.find(')') {
-            e
-        } else {
-            return Platform::Unknown("Unknown".to_string());
+        let (begin, end) = match (string.find('('), string.find(')')) {
+            (Some(begin), Some(end)) => (begin, end),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    "Couldn't detect platform",
+                ))
+            }
         };
         let string = &string[begin + 1..end];
 
-        if string.contains("Win32") || string.contains("Win64") {
+        Ok(if string.contains("Android") {
+            Platform::Android
+        } else if is_ios(string) {
+            Platform::Ios
+        } else if bsd(string).is_some() {
+            Platform::Bsd
+        } else if string.contains("Win32") || string.contains("Win64") {
             Platform::Windows
-        } else if string.contains("Linux") {
+        } else if string.contains("Linux") || string.contains("CrOS") {
             Platform::Linux
         } else if string.contains("Mac OS X") {
             Platform::MacOS
         } else {
             // TODO:
-            // Platform::FreeBsd,
-            // Platform::Ios,
-            // Platform::Android,
             // Platform::Nintendo,
             // Platform::Xbox,
             // Platform::PlayStation,
@@ -197,7 +311,7 @@ impl Target for Os {
             // Platform::Fuchsia,
             // Platform::Redox,
             Platform::Unknown(string.to_string())
-        }
+        })
     }
 
     #[inline(always)]
@@ -208,4 +322,17 @@ impl Target for Os {
             Arch::Wasm32
         })
     }
+
+    fn cpu_cores(self) -> Result<usize> {
+        let cores = window()
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "Window missing"))?
+            .navigator()
+            .hardware_concurrency();
+
+        if cores < 1.0 {
+            return Err(super::err_missing_record());
+        }
+
+        Ok(cores as usize)
+    }
 }
@@ -1,19 +1,21 @@
 use std::{
     convert::TryInto,
-    ffi::OsString,
+    ffi::{OsStr, OsString},
     io::{Error, ErrorKind},
-    mem::MaybeUninit,
+    mem::{size_of, MaybeUninit},
     os::{
-        raw::{c_char, c_int, c_uchar, c_ulong, c_ushort, c_void},
+        raw::{c_char, c_int, c_uchar, c_uint, c_ulong, c_ushort, c_void},
         windows::ffi::OsStringExt,
     },
     ptr,
+    time::{Duration, SystemTime},
 };
 
 use crate::{
     conversions,
-    os::{Os, Target},
-    Arch, DesktopEnv, Platform, Result,
+    os::{Backend, Os},
+    Arch, ColorSupport, DesktopEnv, MeasurementSystem, Platform, Result,
+    USERNAME_MAX_LEN,
 };
 
 #[repr(C)]
@@ -48,6 +50,45 @@ struct SystemInfo {
     processor_revision: c_ushort,
 }
 
+// Source:
+// https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-system_logical_processor_information
+#[repr(C)]
+struct SystemLogicalProcessorInformation {
+    processor_mask: usize,
+    relationship: c_int,
+    // Union of processor core flags, NUMA node number, cache descriptor, and
+    // reserved space
+    reserved: [u64; 2],
+}
+
+// Source:
+// https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/ns-sysinfoapi-memorystatusex
+#[repr(C)]
+struct MemoryStatusEx {
+    length: c_ulong,
+    memory_load: c_ulong,
+    total_phys: u64,
+    avail_phys: u64,
+    total_page_file: u64,
+    avail_page_file: u64,
+    total_virtual: u64,
+    avail_virtual: u64,
+    avail_extended_virtual: u64,
+}
+
+// Source:
+// https://learn.microsoft.com/en-us/windows/win32/api/lmaccess/ns-lmaccess-user_info_11
+//
+// Only the fields up to `full_name` are declared, since the structure is only
+// ever read through a pointer allocated by `NetUserGetInfo()`.
+#[repr(C)]
+struct UserInfo11 {
+    name: *const u16,
+    comment: *const u16,
+    usr_comment: *const u16,
+    full_name: *const u16,
+}
+
 #[allow(unused)]
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -80,9 +121,19 @@ enum ComputerNameFormat {
     Max,
 }
 
+const RELATION_PROCESSOR_CORE: c_int = 0;
+const TOKEN_QUERY: c_ulong = 0x0008;
+const TOKEN_PRIMARY_GROUP: c_int = 5;
+
 const ERR_MORE_DATA: i32 = 0xEA;
 const ERR_INSUFFICIENT_BUFFER: i32 = 0x7A;
 const ERR_NONE_MAPPED: i32 = 0x534;
+const ERR_NO_SUCH_DOMAIN: i32 = 0x54B;
+const RRF_RT_REG_SZ: c_ulong = 0x02;
+const NERR_USER_NOT_FOUND: c_ulong = 2221;
+const LOCALE_IMEASURE: c_ulong = 0x0D;
+const LOCALE_SDECIMAL: c_ulong = 0x0E;
+const LOAD_LIBRARY_SEARCH_SYSTEM32: c_ulong = 0x0800;
 
 #[link(name = "secur32")]
 extern "system" {
@@ -107,38 +158,116 @@ extern "system" {
         pwsz_languages_buffer: *mut u16,
         pcch_languages_buffer: *mut c_ulong,
     ) -> c_int;
+    fn GetSystemPreferredUILanguages(
+        dw_flags: c_ulong,
+        pul_num_languages: *mut c_ulong,
+        pwsz_languages_buffer: *mut u16,
+        pcch_languages_buffer: *mut c_ulong,
+    ) -> c_int;
     fn GetNativeSystemInfo(system_info: *mut SystemInfo);
+    fn GetTickCount64() -> u64;
+    fn GetStdHandle(std_handle: c_ulong) -> *mut c_void;
+    fn GetConsoleMode(console_handle: *mut c_void, mode: *mut c_ulong)
+        -> c_int;
+    fn GetModuleHandleW(module_name: *const u16) -> *mut c_void;
+    fn LoadLibraryExW(
+        filename: *const u16,
+        hfile: *mut c_void,
+        dwflags: c_ulong,
+    ) -> *mut c_void;
+    fn FreeLibrary(hmodule: *mut c_void) -> i32;
+    fn GetProcAddress(
+        module: *mut c_void,
+        proc_name: *const c_char,
+    ) -> *mut c_void;
+    fn GlobalMemoryStatusEx(buffer: *mut MemoryStatusEx) -> c_int;
+    fn GetLogicalProcessorInformation(
+        buffer: *mut SystemLogicalProcessorInformation,
+        return_length: *mut c_ulong,
+    ) -> c_int;
+    fn GetLocaleInfoEx(
+        locale_name: *const u16,
+        lc_type: c_ulong,
+        lc_data: *mut u16,
+        cch_data: c_int,
+    ) -> c_int;
+    fn GetCurrentProcess() -> *mut c_void;
+    fn LocalFree(mem: *mut c_void) -> *mut c_void;
+    fn CloseHandle(handle: *mut c_void) -> c_int;
 }
 
-fn username() -> Result<OsString> {
-    // Step 1. Retreive the entire length of the username
-    let mut size = 0;
-    let fail = unsafe { GetUserNameW(ptr::null_mut(), &mut size) == 0 };
-    assert!(fail);
+#[link(name = "advapi32")]
+extern "system" {
+    fn OpenProcessToken(
+        process_handle: *mut c_void,
+        desired_access: c_ulong,
+        token_handle: *mut *mut c_void,
+    ) -> c_int;
+    fn GetTokenInformation(
+        token_handle: *mut c_void,
+        token_information_class: c_int,
+        token_information: *mut c_void,
+        token_information_length: c_ulong,
+        return_length: *mut c_ulong,
+    ) -> c_int;
+    fn LookupAccountSidW(
+        system_name: *const u16,
+        sid: *mut c_void,
+        name: *mut u16,
+        name_len: *mut c_ulong,
+        referenced_domain_name: *mut u16,
+        referenced_domain_name_len: *mut c_ulong,
+        sid_name_use: *mut c_int,
+    ) -> c_int;
+    fn LookupAccountNameW(
+        system_name: *const u16,
+        account_name: *const u16,
+        sid: *mut c_void,
+        sid_len: *mut c_ulong,
+        referenced_domain_name: *mut u16,
+        referenced_domain_name_len: *mut c_ulong,
+        sid_name_use: *mut c_int,
+    ) -> c_int;
+    fn ConvertSidToStringSidW(
+        sid: *mut c_void,
+        string_sid: *mut *mut u16,
+    ) -> c_int;
+    fn RegGetValueW(
+        hkey: *mut c_void,
+        sub_key: *const u16,
+        value: *const u16,
+        flags: c_ulong,
+        value_type: *mut c_ulong,
+        data: *mut c_void,
+        data_len: *mut c_ulong,
+    ) -> c_int;
+}
 
-    if Error::last_os_error().raw_os_error() != Some(ERR_INSUFFICIENT_BUFFER) {
-        return Err(Error::last_os_error());
-    }
+#[link(name = "netapi32")]
+extern "system" {
+    fn NetUserGetInfo(
+        servername: *const u16,
+        username: *const u16,
+        level: c_ulong,
+        bufptr: *mut *mut c_void,
+    ) -> c_ulong;
+    fn NetApiBufferFree(buffer: *mut c_void) -> c_ulong;
+}
 
-    // Step 2. Allocate memory to put the Windows (UTF-16) string.
-    let mut name: Vec<u16> =
-        Vec::with_capacity(size.try_into().unwrap_or(std::usize::MAX));
-    size = name.capacity().try_into().unwrap_or(std::u32::MAX);
-    let orig_size = size;
+fn username() -> Result<OsString> {
+    // Step 1. Retrieve the username, into a buffer big enough for any username
+    let mut name = [0u16; USERNAME_MAX_LEN + 1];
+    let mut size: c_ulong = name.len().try_into().unwrap_or(std::u32::MAX);
     let fail =
         unsafe { GetUserNameW(name.as_mut_ptr().cast(), &mut size) == 0 };
     if fail {
         return Err(Error::last_os_error());
     }
-    debug_assert_eq!(orig_size, size);
-    unsafe {
-        name.set_len(size.try_into().unwrap_or(std::usize::MAX));
-    }
-    let terminator = name.pop(); // Remove Trailing Null
-    debug_assert_eq!(terminator, Some(0u16));
 
-    // Step 3. Convert to Rust String
-    Ok(OsString::from_wide(&name))
+    // Step 2. Convert to Rust String, without the trailing null
+    let len: usize = size.try_into().unwrap_or(0);
+
+    Ok(OsString::from_wide(&name[..len.saturating_sub(1)]))
 }
 
 fn extended_name(format: ExtendedNameFormat) -> Result<OsString> {
@@ -178,52 +307,685 @@ fn extended_name(format: ExtendedNameFormat) -> Result<OsString> {
     Ok(OsString::from_wide(&name))
 }
 
-impl Target for Os {
-    #[inline(always)]
-    fn langs(self) -> Result<String> {
-        let mut num_languages = 0;
-        let mut buffer_size = 0;
-        let mut buffer;
+/// Read a string value from `HKEY_LOCAL_MACHINE`.
+fn registry_string(sub_key: &str, value: &str) -> Result<OsString> {
+    // `HKEY_LOCAL_MACHINE` is a sign-extended 32-bit constant
+    let hkey = 0x8000_0002_u32 as i32 as isize as *mut c_void;
+    let sub_key: Vec<u16> = sub_key.encode_utf16().chain(Some(0)).collect();
+    let value: Vec<u16> = value.encode_utf16().chain(Some(0)).collect();
+    let get = |data: *mut c_void, data_len: &mut c_ulong| unsafe {
+        RegGetValueW(
+            hkey,
+            sub_key.as_ptr(),
+            value.as_ptr(),
+            RRF_RT_REG_SZ,
+            ptr::null_mut(),
+            data,
+            data_len,
+        )
+    };
+
+    // Step 1. Retrieve the size of the value in bytes
+    let mut data_len = 0;
+    let status = get(ptr::null_mut(), &mut data_len);
+
+    if status != 0 {
+        return Err(Error::from_raw_os_error(status));
+    }
+
+    // Step 2. Read the value, including its NUL terminator
+    let mut data: Vec<u16> = vec![0; (data_len as usize + 1) / 2];
+    let status = get(data.as_mut_ptr().cast(), &mut data_len);
+
+    if status != 0 {
+        return Err(Error::from_raw_os_error(status));
+    }
+
+    data.truncate(data_len as usize / 2);
+
+    while data.last() == Some(&0) {
+        data.pop();
+    }
+
+    if data.is_empty() {
+        return Err(super::err_empty_record());
+    }
+
+    Ok(OsString::from_wide(&data))
+}
+
+/// Get the user's display name without contacting the domain controller, from
+/// the name cached by the logon screen, or else the local account database.
+fn cached_realname() -> Result<OsString> {
+    const LOGON_UI: &str =
+        "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Authentication\\LogonUI";
+
+    if let (Ok(sam_name), Ok(last_user)) = (
+        extended_name(ExtendedNameFormat::SamCompatible),
+        registry_string(LOGON_UI, "LastLoggedOnSAMUser"),
+    ) {
+        let display_name =
+            registry_string(LOGON_UI, "LastLoggedOnDisplayName").ok();
+
+        if let Some(name) =
+            logon_display_name(&sam_name, &last_user, display_name)
+        {
+            return Ok(name);
+        }
+    }
+
+    let username = username()?;
+
+    user_info(&username.to_string_lossy(), |info| info.full_name)
+}
+
+/// Select the display name cached by the logon screen, if it belongs to the
+/// user with the SAM-compatible name `sam_name` (`DOMAIN\user`).
+///
+/// The logon screen only caches the display name of the last user to log on,
+/// `last_user`.
+fn logon_display_name(
+    sam_name: &OsStr,
+    last_user: &OsStr,
+    display_name: Option<OsString>,
+) -> Option<OsString> {
+    if !last_user
+        .to_string_lossy()
+        .eq_ignore_ascii_case(&sam_name.to_string_lossy())
+    {
+        return None;
+    }
+
+    display_name.filter(|name| !name.is_empty())
+}
+
+fn system_info() -> SystemInfo {
+    unsafe {
+        let mut buf = MaybeUninit::uninit();
+        GetNativeSystemInfo(buf.as_mut_ptr());
+        buf.assume_init()
+    }
+}
+
+/// Get the architecture of the machine with `IsWow64Process2()`, which unlike
+/// `GetNativeSystemInfo()`, isn't fooled by x64 emulation on ARM64.
+///
+/// Returns `None` before Windows 10 version 1511, which doesn't have it.
+fn native_machine() -> Option<Arch> {
+    type IsWow64Process2 = unsafe extern "system" fn(
+        process: *mut c_void,
+        process_machine: *mut c_ushort,
+        native_machine: *mut c_ushort,
+    ) -> c_int;
+
+    let kernel32 = "kernel32.dll\0".encode_utf16().collect::<Vec<_>>();
+    let module = unsafe { GetModuleHandleW(kernel32.as_ptr()) };
+
+    if module.is_null() {
+        return None;
+    }
+
+    let function =
+        unsafe { GetProcAddress(module, b"IsWow64Process2\0".as_ptr().cast()) };
+
+    if function.is_null() {
+        return None;
+    }
+
+    let is_wow64_process2: IsWow64Process2 =
+        unsafe { std::mem::transmute(function) };
+    let mut process_machine = 0;
+    let mut native_machine = 0;
+    let ret = unsafe {
+        is_wow64_process2(
+            GetCurrentProcess(),
+            &mut process_machine,
+            &mut native_machine,
+        )
+    };
+
+    if ret == 0 {
+        return None;
+    }
+
+    Some(match native_machine {
+        // IMAGE_FILE_MACHINE_I386
+        0x014C => Arch::I686,
+        // IMAGE_FILE_MACHINE_ARMNT
+        0x01C4 => Arch::ArmV7,
+        // IMAGE_FILE_MACHINE_AMD64
+        0x8664 => Arch::X64,
+        // IMAGE_FILE_MACHINE_ARM64
+        0xAA64 => Arch::Arm64,
+        _ => return None,
+    })
+}
+
+/// Get the console mode of standard output, or `None` if it isn't a console.
+fn stdout_console_mode() -> Option<c_ulong> {
+    const STD_OUTPUT_HANDLE: c_ulong = -11i32 as c_ulong;
+
+    let mut mode = 0;
+    let handle = unsafe { GetStdHandle(STD_OUTPUT_HANDLE) };
+
+    if handle.is_null() || unsafe { GetConsoleMode(handle, &mut mode) } == 0 {
+        return None;
+    }
+
+    Some(mode)
+}
+
+fn cpu_cores_physical() -> Result<usize> {
+    // Step 1. Retrieve the size of the processor information in bytes
+    let mut size = 0;
+    let fail = unsafe {
+        GetLogicalProcessorInformation(ptr::null_mut(), &mut size) == 0
+    };
+
+    assert!(fail);
+
+    if Error::last_os_error().raw_os_error() != Some(ERR_INSUFFICIENT_BUFFER) {
+        return Err(Error::last_os_error());
+    }
+
+    // Step 2. Allocate an (8-byte aligned) buffer for the information
+    let len: usize = size.try_into().unwrap_or(std::usize::MAX);
+    let mut buffer: Vec<u64> = Vec::with_capacity(len / 8 + 1);
+    let info: *mut SystemLogicalProcessorInformation =
+        buffer.as_mut_ptr().cast();
+    let fail = unsafe { GetLogicalProcessorInformation(info, &mut size) == 0 };
+
+    if fail {
+        return Err(Error::last_os_error());
+    }
+
+    // Step 3. Count the entries describing a processor core
+    let end = info.cast::<u8>().wrapping_add(size.try_into().unwrap_or(0));
+    let mut entry = info;
+    let mut cores = 0;
+
+    while entry.wrapping_add(1).cast::<u8>() <= end {
+        if unsafe { (*entry).relationship } == RELATION_PROCESSOR_CORE {
+            cores += 1;
+        }
+
+        entry = entry.wrapping_add(1);
+    }
+
+    Ok(cores)
+}
+
+fn primary_group() -> Result<OsString> {
+    // Step 1. Open the access token of the current process
+    let mut token = ptr::null_mut();
+    let fail = unsafe {
+        OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0
+    };
+    if fail {
+        return Err(Error::last_os_error());
+    }
+
+    // Step 2. Retrieve the primary group SID from the token
+    let mut size = 0;
+    let fail = unsafe {
+        GetTokenInformation(
+            token,
+            TOKEN_PRIMARY_GROUP,
+            ptr::null_mut(),
+            0,
+            &mut size,
+        ) == 0
+    };
+
+    assert!(fail);
+
+    if Error::last_os_error().raw_os_error() != Some(ERR_INSUFFICIENT_BUFFER) {
+        let error = Error::last_os_error();
+        unsafe { CloseHandle(token) };
+        return Err(error);
+    }
+
+    let mut info: Vec<u8> =
+        Vec::with_capacity(size.try_into().unwrap_or(std::usize::MAX));
+    let fail = unsafe {
+        GetTokenInformation(
+            token,
+            TOKEN_PRIMARY_GROUP,
+            info.as_mut_ptr().cast(),
+            size,
+            &mut size,
+        ) == 0
+    };
+    let error = Error::last_os_error();
+
+    unsafe { CloseHandle(token) };
+
+    if fail {
+        return Err(error);
+    }
+
+    // `TOKEN_PRIMARY_GROUP` is a struct containing a single `PSID`
+    let sid = unsafe { info.as_ptr().cast::<*mut c_void>().read_unaligned() };
+
+    // Step 3. Retrieve the entire length of the group name
+    let mut name_len = 0;
+    let mut domain_len = 0;
+    let mut sid_name_use = 0;
+    let fail = unsafe {
+        LookupAccountSidW(
+            ptr::null(),
+            sid,
+            ptr::null_mut(),
+            &mut name_len,
+            ptr::null_mut(),
+            &mut domain_len,
+            &mut sid_name_use,
+        ) == 0
+    };
+
+    assert!(fail);
+
+    let last_err = Error::last_os_error().raw_os_error();
+
+    if last_err == Some(ERR_NONE_MAPPED) {
+        return Err(super::err_missing_record());
+    }
+
+    if last_err != Some(ERR_INSUFFICIENT_BUFFER) {
+        return Err(Error::last_os_error());
+    }
+
+    // Step 4. Allocate memory to put the Windows (UTF-16) strings.
+    let mut name: Vec<u16> =
+        Vec::with_capacity(name_len.try_into().unwrap_or(std::usize::MAX));
+    let mut domain: Vec<u16> =
+        Vec::with_capacity(domain_len.try_into().unwrap_or(std::usize::MAX));
+    let fail = unsafe {
+        LookupAccountSidW(
+            ptr::null(),
+            sid,
+            name.as_mut_ptr(),
+            &mut name_len,
+            domain.as_mut_ptr(),
+            &mut domain_len,
+            &mut sid_name_use,
+        ) == 0
+    };
+    if fail {
+        return Err(Error::last_os_error());
+    }
+
+    unsafe { name.set_len(name_len.try_into().unwrap_or(std::usize::MAX)) };
+
+    // Step 5. Convert to Rust String
+    Ok(OsString::from_wide(&name))
+}
+
+/// Get the OS version from `RtlGetVersion()`, which unlike `GetVersionEx()`
+/// isn't affected by the application's compatibility manifest.
+fn os_version_info() -> Result<OsVersionInfoEx> {
+    // Due to MingW Limitations, we must dynamically load ntdll.dll
+    extern "system" {
+        fn LoadLibraryExW(
+            filename: *const u16,
+            hfile: *mut c_void,
+            dwflags: c_ulong,
+        ) -> *mut c_void;
+        fn FreeLibrary(hmodule: *mut c_void) -> i32;
+        fn GetProcAddress(
+            hmodule: *mut c_void,
+            procname: *const c_char,
+        ) -> *mut c_void;
+    }
+
+    let mut path = "ntdll.dll\0".encode_utf16().collect::<Vec<u16>>();
+    let path = path.as_mut_ptr();
+
+    let inst = unsafe { LoadLibraryExW(path, ptr::null_mut(), 0x0000_0800) };
+
+    if inst.is_null() {
+        return Err(Error::last_os_error());
+    }
+
+    let mut path = "RtlGetVersion\0".bytes().collect::<Vec<u8>>();
+    let path = path.as_mut_ptr().cast();
+    let func = unsafe { GetProcAddress(inst, path) };
+
+    if func.is_null() {
+        if unsafe { FreeLibrary(inst) } == 0 {
+            return Err(Error::last_os_error());
+        }
+
+        return Err(Error::last_os_error());
+    }
+
+    let get_version: unsafe extern "system" fn(a: *mut OsVersionInfoEx) -> u32 =
+        unsafe { std::mem::transmute(func) };
+
+    let mut version = MaybeUninit::<OsVersionInfoEx>::zeroed();
+
+    unsafe {
+        (*version.as_mut_ptr()).os_version_info_size =
+            size_of::<OsVersionInfoEx>() as u32;
+        get_version(version.as_mut_ptr());
+
+        if FreeLibrary(inst) == 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(version.assume_init())
+    }
+}
+
+/// Get the profile directory of the user with the given username, from the
+/// profile list in the registry.
+///
+/// Unlike the home directory from `NetUserGetInfo()`, which is only set when
+/// assigned in Active Directory, every user that has logged on has one.
+fn profile_dir(username: &str) -> Result<OsString> {
+    const PROFILE_LIST: &str =
+        "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\ProfileList";
+
+    if username.contains('\0') {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Username contains NUL byte",
+        ));
+    }
+
+    // Step 1. Convert to a NUL-terminated Windows (UTF-16) string
+    let username: Vec<u16> = username.encode_utf16().chain(Some(0)).collect();
+
+    // Step 2. Retrieve the length of the user's SID
+    let mut sid_len = 0;
+    let mut domain_len = 0;
+    let mut sid_name_use = 0;
+    let fail = unsafe {
+        LookupAccountNameW(
+            ptr::null(),
+            username.as_ptr(),
+            ptr::null_mut(),
+            &mut sid_len,
+            ptr::null_mut(),
+            &mut domain_len,
+            &mut sid_name_use,
+        ) == 0
+    };
+
+    assert!(fail);
+
+    let last_err = Error::last_os_error().raw_os_error();
+
+    if last_err == Some(ERR_NONE_MAPPED) {
+        return Err(Error::new(ErrorKind::NotFound, "User not found"));
+    }
+
+    if last_err != Some(ERR_INSUFFICIENT_BUFFER) {
+        return Err(Error::last_os_error());
+    }
+
+    // Step 3. Retrieve the SID (into a 4-byte aligned buffer)
+    let mut sid: Vec<u32> = vec![0; (sid_len as usize + 3) / 4];
+    let mut domain: Vec<u16> = vec![0; domain_len as usize];
+    let fail = unsafe {
+        LookupAccountNameW(
+            ptr::null(),
+            username.as_ptr(),
+            sid.as_mut_ptr().cast(),
+            &mut sid_len,
+            domain.as_mut_ptr(),
+            &mut domain_len,
+            &mut sid_name_use,
+        ) == 0
+    };
+
+    if fail {
+        return Err(Error::last_os_error());
+    }
+
+    // Step 4. Convert the SID to a string (example: "S-1-5-21-...-1001")
+    let mut string_sid = ptr::null_mut();
+    let fail = unsafe {
+        ConvertSidToStringSidW(sid.as_mut_ptr().cast(), &mut string_sid) == 0
+    };
+
+    if fail {
+        return Err(Error::last_os_error());
+    }
+
+    let mut sid = Vec::new();
+    let mut c = string_sid;
+
+    unsafe {
+        while *c != 0 {
+            sid.push(*c);
+            c = c.wrapping_add(1);
+        }
+
+        LocalFree(string_sid.cast());
+    }
+
+    // Step 5. Read the profile directory (with environment variables, such as
+    // `%SystemDrive%`, expanded)
+    let sub_key =
+        format!("{}\\{}", PROFILE_LIST, String::from_utf16_lossy(&sid));
+
+    registry_string(&sub_key, "ProfileImagePath")
+}
+
+fn user_info(
+    username: &str,
+    field: fn(&UserInfo11) -> *const u16,
+) -> Result<OsString> {
+    if username.contains('\0') {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Username contains NUL byte",
+        ));
+    }
+
+    // Step 1. Convert to a NUL-terminated Windows (UTF-16) string
+    let username: Vec<u16> = username.encode_utf16().chain(Some(0)).collect();
+
+    // Step 2. Retrieve the user's information
+    let mut info = ptr::null_mut();
+    let status = unsafe {
+        NetUserGetInfo(ptr::null(), username.as_ptr(), 11, &mut info)
+    };
+
+    if status == NERR_USER_NOT_FOUND {
+        return Err(Error::new(ErrorKind::NotFound, "User not found"));
+    }
+
+    if status != 0 {
+        return Err(Error::from_raw_os_error(status as i32));
+    }
+
+    // Step 3. Copy the field out of the buffer before freeing it
+    let string = unsafe { field(&*info.cast::<UserInfo11>()) };
+    let mut name = Vec::new();
+
+    if !string.is_null() {
+        let mut c = string;
 
         unsafe {
-            assert_ne!(
-                GetUserPreferredUILanguages(
-                    0x08, /* MUI_LANGUAGE_NAME */
-                    &mut num_languages,
-                    ptr::null_mut(), // List of languages.
-                    &mut buffer_size,
-                ),
-                0
-            );
-
-            buffer = Vec::with_capacity(buffer_size as usize);
-
-            assert_ne!(
-                GetUserPreferredUILanguages(
-                    0x08, /* MUI_LANGUAGE_NAME */
-                    &mut num_languages,
-                    buffer.as_mut_ptr(), // List of languages.
-                    &mut buffer_size,
-                ),
-                0
-            );
-
-            buffer.set_len(buffer_size as usize);
+            while *c != 0 {
+                name.push(*c);
+                c = c.wrapping_add(1);
+            }
         }
+    }
 
-        // We know it ends in two null characters.
-        buffer.pop();
-        buffer.pop();
+    unsafe { NetApiBufferFree(info) };
 
-        // Combine into a single string
-        Ok(String::from_utf16_lossy(&buffer)
-            .split('\0')
-            .collect::<Vec<&str>>()
-            .join(";"))
+    if name.is_empty() {
+        return Err(super::err_empty_record());
+    }
+
+    // Step 4. Convert to Rust String
+    Ok(OsString::from_wide(&name))
+}
+
+/// Get a list of UI languages with `GetUserPreferredUILanguages()` or
+/// `GetSystemPreferredUILanguages()`.
+fn preferred_ui_languages(
+    get: unsafe extern "system" fn(
+        c_ulong,
+        *mut c_ulong,
+        *mut u16,
+        *mut c_ulong,
+    ) -> c_int,
+) -> Result<Vec<String>> {
+    let mut num_languages = 0;
+    let mut buffer_size = 0;
+    let mut buffer;
+
+    unsafe {
+        if get(
+            0x08, /* MUI_LANGUAGE_NAME */
+            &mut num_languages,
+            ptr::null_mut(), // List of languages.
+            &mut buffer_size,
+        ) == 0
+        {
+            return Err(Error::last_os_error());
+        }
+
+        buffer = Vec::with_capacity(buffer_size as usize);
+
+        if get(
+            0x08, /* MUI_LANGUAGE_NAME */
+            &mut num_languages,
+            buffer.as_mut_ptr(), // List of languages.
+            &mut buffer_size,
+        ) == 0
+        {
+            return Err(Error::last_os_error());
+        }
+
+        buffer.set_len(buffer_size as usize);
+    }
+
+    // We know it ends in two null characters.
+    buffer.pop();
+    buffer.pop();
+
+    Ok(String::from_utf16_lossy(&buffer)
+        .split('\0')
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// Get a value of the user's default locale.
+fn locale_info(lc_type: c_ulong) -> Result<String> {
+    // Values queried are short (the decimal separator is at most 3 characters)
+    let mut buffer = [0u16; 16];
+    let len = unsafe {
+        GetLocaleInfoEx(
+            ptr::null(), // LOCALE_NAME_USER_DEFAULT
+            lc_type,
+            buffer.as_mut_ptr(),
+            buffer.len().try_into().unwrap_or(std::i32::MAX),
+        )
+    };
+
+    if len == 0 {
+        return Err(Error::last_os_error());
+    }
+
+    // Remove the trailing NUL
+    let len = (len - 1).try_into().unwrap_or(0);
+
+    String::from_utf16(&buffer[..len])
+        .map_err(|_| Error::new(ErrorKind::InvalidData, "Invalid UTF-16"))
+}
+
+/// Get the scale factor of the screen, from the system DPI.
+fn display_scale() -> Result<f32> {
+    type GetDpiForSystem = unsafe extern "system" fn() -> c_uint;
+
+    // `GetDpiForSystem()` is only available on Windows 10 version 1607 and
+    // later, and loading it at runtime avoids linking to user32.dll
+    let user32 = "user32.dll\0".encode_utf16().collect::<Vec<_>>();
+    let module = unsafe {
+        LoadLibraryExW(
+            user32.as_ptr(),
+            ptr::null_mut(),
+            LOAD_LIBRARY_SEARCH_SYSTEM32,
+        )
+    };
+
+    if module.is_null() {
+        return Err(Error::last_os_error());
+    }
+
+    let function =
+        unsafe { GetProcAddress(module, b"GetDpiForSystem\0".as_ptr().cast()) };
+    let dpi = if function.is_null() {
+        None
+    } else {
+        let get_dpi_for_system: GetDpiForSystem =
+            unsafe { std::mem::transmute(function) };
+
+        Some(unsafe { get_dpi_for_system() })
+    };
+
+    unsafe { FreeLibrary(module) };
+
+    let dpi = dpi.ok_or_else(super::err_unsupported)?;
+
+    if dpi == 0 {
+        return Err(super::err_missing_record());
+    }
+
+    Ok(dpi as f32 / 96.0)
+}
+
+impl Backend for Os {
+    #[inline(always)]
+    fn display_scale(self) -> Result<f32> {
+        display_scale()
+    }
+
+    #[inline(always)]
+    fn measurement_system(self) -> Result<MeasurementSystem> {
+        match locale_info(LOCALE_IMEASURE)?.as_str() {
+            "0" => Ok(MeasurementSystem::Metric),
+            "1" => Ok(MeasurementSystem::Uscs),
+            _ => Err(Error::new(ErrorKind::InvalidData, "Parsing failed")),
+        }
+    }
+
+    fn decimal_separator(self) -> Result<char> {
+        locale_info(LOCALE_SDECIMAL)?
+            .chars()
+            .next()
+            .ok_or_else(super::err_empty_record)
+    }
+
+    fn langs_raw(self) -> Result<Vec<String>> {
+        preferred_ui_languages(GetUserPreferredUILanguages)
+    }
+
+    fn system_langs_raw(self) -> Result<Vec<String>> {
+        preferred_ui_languages(GetSystemPreferredUILanguages)
     }
 
     fn realname(self) -> Result<OsString> {
-        extended_name(ExtendedNameFormat::Display)
+        match extended_name(ExtendedNameFormat::Display) {
+            // The domain controller can't be reached, as when working offline
+            Err(e) if e.raw_os_error() == Some(ERR_NO_SUCH_DOMAIN) => {
+                cached_realname()
+            }
+            result => result,
+        }
+    }
+
+    fn realname_given_family(self) -> Result<(OsString, OsString)> {
+        Ok((
+            extended_name(ExtendedNameFormat::GivenName)?,
+            extended_name(ExtendedNameFormat::Surname)?,
+        ))
     }
 
     fn username(self) -> Result<OsString> {
@@ -312,60 +1074,49 @@ impl Target for Os {
         conversions::string_from_os(OsString::from_wide(&name))
     }
 
-    fn distro(self) -> Result<String> {
-        // Due to MingW Limitations, we must dynamically load ntdll.dll
-        extern "system" {
-            fn LoadLibraryExW(
-                filename: *const u16,
-                hfile: *mut c_void,
-                dwflags: c_ulong,
-            ) -> *mut c_void;
-            fn FreeLibrary(hmodule: *mut c_void) -> i32;
-            fn GetProcAddress(
-                hmodule: *mut c_void,
-                procname: *const c_char,
-            ) -> *mut c_void;
-        }
-
-        let mut path = "ntdll.dll\0".encode_utf16().collect::<Vec<u16>>();
-        let path = path.as_mut_ptr();
+    fn fqdn(self) -> Result<String> {
+        // Step 1. Retreive the entire length of the domain name
+        let mut size = 0;
+        let fail = unsafe {
+            // Ignore error, we know that it will be ERROR_INSUFFICIENT_BUFFER
+            GetComputerNameExW(
+                ComputerNameFormat::DnsFullyQualified,
+                ptr::null_mut(),
+                &mut size,
+            ) == 0
+        };
 
-        let inst =
-            unsafe { LoadLibraryExW(path, ptr::null_mut(), 0x0000_0800) };
+        assert!(fail);
 
-        if inst.is_null() {
+        if Error::last_os_error().raw_os_error() != Some(ERR_MORE_DATA) {
             return Err(Error::last_os_error());
         }
 
-        let mut path = "RtlGetVersion\0".bytes().collect::<Vec<u8>>();
-        let path = path.as_mut_ptr().cast();
-        let func = unsafe { GetProcAddress(inst, path) };
-
-        if func.is_null() {
-            if unsafe { FreeLibrary(inst) } == 0 {
-                return Err(Error::last_os_error());
-            }
+        // Step 2. Allocate memory to put the Windows (UTF-16) string.
+        let mut name: Vec<u16> =
+            Vec::with_capacity(size.try_into().unwrap_or(std::usize::MAX));
+        let mut size = name.capacity().try_into().unwrap_or(std::u32::MAX);
 
+        if unsafe {
+            GetComputerNameExW(
+                ComputerNameFormat::DnsFullyQualified,
+                name.as_mut_ptr().cast(),
+                &mut size,
+            ) == 0
+        } {
             return Err(Error::last_os_error());
         }
 
-        let get_version: unsafe extern "system" fn(
-            a: *mut OsVersionInfoEx,
-        ) -> u32 = unsafe { std::mem::transmute(func) };
-
-        let mut version = MaybeUninit::<OsVersionInfoEx>::zeroed();
-
-        let version = unsafe {
-            (*version.as_mut_ptr()).os_version_info_size =
-                std::mem::size_of::<OsVersionInfoEx>() as u32;
-            get_version(version.as_mut_ptr());
+        unsafe {
+            name.set_len(size.try_into().unwrap_or(std::usize::MAX));
+        }
 
-            if FreeLibrary(inst) == 0 {
-                return Err(Error::last_os_error());
-            }
+        // Step 3. Convert to Rust String
+        conversions::string_from_os(OsString::from_wide(&name))
+    }
 
-            version.assume_init()
-        };
+    fn distro(self) -> Result<String> {
+        let version = os_version_info()?;
 
         let product = match version.product_type {
             1 => "Workstation",
@@ -383,14 +1134,24 @@ impl Target for Os {
         ))
     }
 
+    fn os_version_raw(self) -> Result<(u32, u32, u32)> {
+        let version = os_version_info()?;
+
+        Ok((
+            version.major_version,
+            version.minor_version,
+            version.build_number,
+        ))
+    }
+
     #[inline(always)]
-    fn desktop_env(self) -> DesktopEnv {
-        DesktopEnv::Windows
+    fn desktop_env(self) -> Result<DesktopEnv> {
+        Ok(DesktopEnv::Windows)
     }
 
     #[inline(always)]
-    fn platform(self) -> Platform {
-        Platform::Windows
+    fn platform(self) -> Result<Platform> {
+        Ok(Platform::current())
     }
 
     #[inline(always)]
@@ -411,11 +1172,7 @@ impl Target for Os {
             })
         }
 
-        let buf: SystemInfo = unsafe {
-            let mut buf = MaybeUninit::uninit();
-            GetNativeSystemInfo(buf.as_mut_ptr());
-            buf.assume_init()
-        };
+        let buf = system_info();
 
         // Supported architectures, source:
         // https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/ns-sysinfoapi-system_info#members
@@ -446,6 +1203,77 @@ impl Target for Os {
         })
     }
 
+    fn boot_time(self) -> Result<SystemTime> {
+        // Milliseconds since the system was started
+        let uptime = Duration::from_millis(unsafe { GetTickCount64() });
+
+        SystemTime::now().checked_sub(uptime).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidData, "Boot time out of range")
+        })
+    }
+
+    fn tty(self) -> bool {
+        stdout_console_mode().is_some()
+    }
+
+    fn color_support(self) -> ColorSupport {
+        const ENABLE_VIRTUAL_TERMINAL_PROCESSING: c_ulong = 0x0004;
+
+        let mode = if let Some(mode) = stdout_console_mode() {
+            mode
+        } else {
+            return ColorSupport::None;
+        };
+
+        if std::env::var_os("NO_COLOR").map_or(false, |v| !v.is_empty()) {
+            ColorSupport::None
+        } else if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 {
+            // Windows 10 and later consoles support 24-bit color
+            ColorSupport::TrueColor
+        } else {
+            ColorSupport::None
+        }
+    }
+
+    fn arch_native(self) -> Result<Arch> {
+        native_machine().map(Ok).unwrap_or_else(|| self.arch())
+    }
+
+    #[inline(always)]
+    fn cpu_cores(self) -> Result<usize> {
+        Ok(system_info()
+            .number_of_processors
+            .try_into()
+            .unwrap_or(std::usize::MAX))
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn hypervisor(self) -> Result<Option<String>> {
+        Ok(super::cpuid_hypervisor())
+    }
+
+    #[inline(always)]
+    fn cpu_cores_physical(self) -> Result<usize> {
+        cpu_cores_physical()
+    }
+
+    #[inline(always)]
+    fn memory_total(self) -> Result<u64> {
+        let mut status = MaybeUninit::<MemoryStatusEx>::zeroed();
+
+        let status = unsafe {
+            (*status.as_mut_ptr()).length = size_of::<MemoryStatusEx>() as u32;
+
+            if GlobalMemoryStatusEx(status.as_mut_ptr()) == 0 {
+                return Err(Error::last_os_error());
+            }
+
+            status.assume_init()
+        };
+
+        Ok(status.total_phys)
+    }
+
     #[inline(always)]
     fn account(self) -> Result<OsString> {
         match extended_name(ExtendedNameFormat::UserPrincipal) {
@@ -454,4 +1282,55 @@ impl Target for Os {
             Err(e) => Err(e),
         }
     }
+
+    #[inline(always)]
+    fn primary_group(self) -> Result<OsString> {
+        primary_group()
+    }
+
+    fn realname_of(self, username: &str) -> Result<OsString> {
+        user_info(username, |info| info.full_name)
+    }
+
+    fn home_dir_of(self, username: &str) -> Result<OsString> {
+        profile_dir(username)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logon_display_name_for_last_user() {
+        let name = logon_display_name(
+            OsStr::new("CORP\\alice"),
+            OsStr::new("corp\\Alice"),
+            Some("Alice Liddell".into()),
+        );
+
+        assert_eq!(name, Some("Alice Liddell".into()));
+    }
+
+    #[test]
+    fn logon_display_name_for_other_user() {
+        let name = logon_display_name(
+            OsStr::new("CORP\\bob"),
+            OsStr::new("CORP\\alice"),
+            Some("Alice Liddell".into()),
+        );
+
+        assert_eq!(name, None);
+    }
+
+    #[test]
+    fn logon_display_name_missing() {
+        let sam_name = OsStr::new("CORP\\alice");
+
+        assert_eq!(logon_display_name(sam_name, sam_name, None), None);
+        assert_eq!(
+            logon_display_name(sam_name, sam_name, Some(OsString::new())),
+            None,
+        );
+    }
 }
@@ -0,0 +1,21 @@
+use std::ffi::OsString;
+
+/// The current user's account record, from one lookup of the user database
+///
+/// Returned by [`fallible::passwd()`](crate::fallible::passwd).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Passwd {
+    /// The username (`pw_name`)
+    pub name: OsString,
+    /// The full name, from the first field of GECOS; empty if not set
+    pub realname: OsString,
+    /// The user ID (`pw_uid`)
+    pub uid: u32,
+    /// The primary group ID (`pw_gid`)
+    pub gid: u32,
+    /// The home directory (`pw_dir`); empty if not set
+    pub home: OsString,
+    /// The login shell (`pw_shell`); empty if not set
+    pub shell: OsString,
+}
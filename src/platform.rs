@@ -1,8 +1,11 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
 
 /// The underlying platform for a system
 #[allow(missing_docs)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 #[non_exhaustive]
 pub enum Platform {
     Linux,
@@ -14,24 +17,351 @@ pub enum Platform {
     Illumos,
     Ios,
     Android,
-    // FIXME: Separate for different Nintendo consoles in whoami 2.0.0,
-    // currently only used for 3DS
+    // FIXME: Rename to 'Nintendo3ds' in whoami 2.0.0, currently only used for
+    // 3DS
     Nintendo,
+    NintendoSwitch,
     // FIXME: Currently unused, remove in whoami 2.0.0
     Xbox,
     PlayStation,
     Fuchsia,
     Redox,
+    Haiku,
+    Wasi,
+    WebAssembly,
     Unknown(String),
 }
 
+/// The C library used by an operating system
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[non_exhaustive]
+pub enum LibcKind {
+    /// The GNU C Library
+    Glibc,
+    /// musl libc
+    Musl,
+    /// Unknown C library
+    Unknown,
+}
+
+impl Display for LibcKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Glibc => "glibc",
+            Self::Musl => "musl",
+            Self::Unknown => "Unknown",
+        })
+    }
+}
+
+/// The native package format of an operating system
+///
+/// Displayed as the file extension, without the leading dot (example: "deb").
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[non_exhaustive]
+pub enum PackageFormat {
+    /// Debian packages (`.deb`)
+    Deb,
+    /// RPM packages (`.rpm`), as used by Fedora, RHEL and SUSE
+    Rpm,
+    /// Arch Linux pacman packages (`.pkg.tar.zst`)
+    Pacman,
+    /// Alpine packages (`.apk`)
+    Apk,
+    /// Mac OS installer packages (`.pkg`)
+    Pkg,
+    /// Windows Installer packages (`.msi`)
+    Msi,
+    /// Flatpak bundles (`.flatpak`), when running inside a Flatpak sandbox
+    Flatpak,
+    /// Unknown package format
+    Unknown,
+}
+
+impl Display for PackageFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Deb => "deb",
+            Self::Rpm => "rpm",
+            Self::Pacman => "pkg.tar.zst",
+            Self::Apk => "apk",
+            Self::Pkg => "pkg",
+            Self::Msi => "msi",
+            Self::Flatpak => "flatpak",
+            Self::Unknown => "Unknown",
+        })
+    }
+}
+
+/// The kind of a [`Platform`], without the payload of its `Unknown` variant.
+///
+/// Each variant corresponds to the [`Platform`] variant of the same name.
+/// Unlike [`Platform`], this is [`Copy`].
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[non_exhaustive]
+pub enum PlatformKind {
+    Linux,
+    Bsd,
+    Windows,
+    MacOS,
+    Illumos,
+    Ios,
+    Android,
+    Nintendo,
+    NintendoSwitch,
+    Xbox,
+    PlayStation,
+    Fuchsia,
+    Redox,
+    Haiku,
+    Wasi,
+    WebAssembly,
+    Unknown,
+}
+
 impl Display for Platform {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         if let Self::Unknown(_) = self {
             f.write_str("Unknown: ")?;
         }
 
-        f.write_str(match self {
+        f.write_str(self.name())
+    }
+}
+
+impl Platform {
+    /// Get the platform this crate was compiled for, without querying the
+    /// operating system.
+    ///
+    /// Unlike [`platform()`](crate::platform), this doesn't detect the host
+    /// operating system when running in a web browser.
+    #[inline(always)]
+    pub fn current() -> Self {
+        if cfg!(target_os = "daku") {
+            Self::Unknown("Daku".to_string())
+        } else if cfg!(target_os = "wasi") {
+            Self::Wasi
+        } else if cfg!(target_os = "windows") {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOS
+        } else if cfg!(target_os = "redox") {
+            Self::Redox
+        } else if cfg!(target_os = "linux") {
+            Self::Linux
+        } else if cfg!(target_os = "android") {
+            Self::Android
+        } else if cfg!(target_os = "tvos") {
+            Self::Unknown("tvOS".to_string())
+        } else if cfg!(target_os = "watchos") {
+            Self::Unknown("watchOS".to_string())
+        } else if cfg!(target_os = "ios") {
+            Self::Ios
+        } else if cfg!(target_os = "fuchsia") {
+            Self::Fuchsia
+        } else if cfg!(target_os = "illumos") {
+            Self::Illumos
+        } else if cfg!(any(
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+        )) {
+            Self::Bsd
+        } else if cfg!(target_os = "haiku") {
+            Self::Haiku
+        } else if cfg!(target_os = "vxworks") {
+            Self::Unknown("VxWorks".to_string())
+        } else if cfg!(target_os = "nto") {
+            Self::Unknown("QNX Neutrino".to_string())
+        } else if cfg!(all(target_os = "horizon", target_arch = "aarch64")) {
+            Self::NintendoSwitch
+        } else if cfg!(target_os = "horizon") {
+            Self::Nintendo
+        } else if cfg!(target_os = "vita") {
+            Self::PlayStation
+        } else if cfg!(target_os = "hurd") {
+            Self::Unknown("GNU Hurd".to_string())
+        } else if cfg!(target_os = "aix") {
+            Self::Unknown("AIX OS".to_string())
+        } else if cfg!(target_os = "espidf") {
+            Self::Unknown("ESP-IDF".to_string())
+        } else if cfg!(target_os = "emscripten") {
+            Self::Unknown("Emscripten".to_string())
+        } else if cfg!(target_os = "solaris") {
+            Self::Unknown("Solaris".to_string())
+        } else if cfg!(target_os = "l4re") {
+            Self::Unknown("L4 Runtime Environment".to_string())
+        } else if cfg!(any(target_arch = "wasm32", target_arch = "wasm64")) {
+            Self::WebAssembly
+        } else {
+            Self::Unknown("Unknown".to_string())
+        }
+    }
+
+    /// Parse the operating system of a Rust target triple, independent of the
+    /// target this crate was compiled for.
+    ///
+    /// Example: `Platform::MacOS` for "aarch64-apple-darwin"
+    pub fn from_target_str(target: &str) -> Self {
+        let parts = target.split('-').skip(1).collect::<Vec<_>>();
+        let has = |name: &str| parts.contains(&name);
+
+        if has("android") || has("androideabi") {
+            Self::Android
+        } else if has("linux") {
+            Self::Linux
+        } else if has("windows") {
+            Self::Windows
+        } else if has("darwin") || has("macos") {
+            Self::MacOS
+        } else if has("ios") {
+            Self::Ios
+        } else if has("tvos") {
+            Self::Unknown("tvOS".to_string())
+        } else if has("watchos") {
+            Self::Unknown("watchOS".to_string())
+        } else if has("freebsd")
+            || has("netbsd")
+            || has("openbsd")
+            || has("dragonfly")
+        {
+            Self::Bsd
+        } else if has("illumos") {
+            Self::Illumos
+        } else if has("solaris") {
+            Self::Unknown("Solaris".to_string())
+        } else if has("fuchsia") {
+            Self::Fuchsia
+        } else if has("redox") {
+            Self::Redox
+        } else if has("haiku") {
+            Self::Haiku
+        } else if has("3ds") {
+            Self::Nintendo
+        } else if has("switch") {
+            Self::NintendoSwitch
+        } else if has("vita") {
+            Self::PlayStation
+        } else if has("emscripten") {
+            Self::Unknown("Emscripten".to_string())
+        } else if parts.iter().any(|part| part.starts_with("wasi")) {
+            Self::Wasi
+        } else if target.starts_with("wasm") {
+            Self::WebAssembly
+        } else {
+            Self::Unknown("Unknown".to_string())
+        }
+    }
+
+    /// Get the coarse family of this platform: "unix", "windows", "wasm" or
+    /// "unknown".
+    ///
+    /// This matches Rust's notion of `target_family`.  `Unknown` platforms map
+    /// to "unknown", except for Daku, which maps to "wasm".
+    pub fn family(&self) -> &'static str {
+        match self {
+            Self::Linux
+            | Self::Bsd
+            | Self::MacOS
+            | Self::Illumos
+            | Self::Ios
+            | Self::Android
+            | Self::Nintendo
+            | Self::PlayStation
+            | Self::Fuchsia
+            | Self::Redox
+            | Self::Haiku => "unix",
+            Self::Windows | Self::Xbox => "windows",
+            Self::NintendoSwitch => "unknown",
+            Self::Wasi | Self::WebAssembly => "wasm",
+            Self::Unknown(name) if name == "Daku" => "wasm",
+            Self::Unknown(_) => "unknown",
+        }
+    }
+
+    /// Get the `target_os` identifier of this platform, as used with `cfg`.
+    ///
+    /// Returns `None` for `Unknown` platforms, for `Bsd` (which covers several
+    /// `target_os` values), and for platforms without a `target_os` of their
+    /// own (`Xbox` and `WebAssembly`).
+    ///
+    /// Example: "macos" for `Platform::MacOS`
+    pub fn target_os(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::Linux => "linux",
+            Self::Windows => "windows",
+            Self::MacOS => "macos",
+            Self::Illumos => "illumos",
+            Self::Ios => "ios",
+            Self::Android => "android",
+            Self::Nintendo | Self::NintendoSwitch => "horizon",
+            Self::PlayStation => "vita",
+            Self::Fuchsia => "fuchsia",
+            Self::Redox => "redox",
+            Self::Haiku => "haiku",
+            Self::Wasi => "wasi",
+            Self::Bsd | Self::Xbox | Self::WebAssembly | Self::Unknown(_) => {
+                return None
+            }
+        })
+    }
+
+    /// Get the path separator of this platform: `\` on Windows and Xbox, and
+    /// `/` everywhere else (including `Unknown` platforms).
+    ///
+    /// Combined with [`from_target_str()`](Platform::from_target_str), this
+    /// can be used for a platform other than the one this program runs on.
+    pub fn path_separator(&self) -> char {
+        match self {
+            Self::Windows | Self::Xbox => '\\',
+            _ => '/',
+        }
+    }
+
+    /// Get the line ending of this platform: `"\r\n"` on Windows and Xbox, and
+    /// `"\n"` everywhere else (including `Unknown` platforms).
+    pub fn line_ending(&self) -> &'static str {
+        match self {
+            Self::Windows | Self::Xbox => "\r\n",
+            _ => "\n",
+        }
+    }
+
+    /// Get the [`PlatformKind`] of this platform.
+    pub fn kind(&self) -> PlatformKind {
+        match self {
+            Self::Linux => PlatformKind::Linux,
+            Self::Bsd => PlatformKind::Bsd,
+            Self::Windows => PlatformKind::Windows,
+            Self::MacOS => PlatformKind::MacOS,
+            Self::Illumos => PlatformKind::Illumos,
+            Self::Ios => PlatformKind::Ios,
+            Self::Android => PlatformKind::Android,
+            Self::Nintendo => PlatformKind::Nintendo,
+            Self::NintendoSwitch => PlatformKind::NintendoSwitch,
+            Self::Xbox => PlatformKind::Xbox,
+            Self::PlayStation => PlatformKind::PlayStation,
+            Self::Fuchsia => PlatformKind::Fuchsia,
+            Self::Redox => PlatformKind::Redox,
+            Self::Haiku => PlatformKind::Haiku,
+            Self::Wasi => PlatformKind::Wasi,
+            Self::WebAssembly => PlatformKind::WebAssembly,
+            Self::Unknown(_) => PlatformKind::Unknown,
+        }
+    }
+
+    /// Get the name of this platform, without allocating.
+    ///
+    /// Unlike the [`Display`] implementation, the name of an `Unknown` variant
+    /// is not prefixed with "Unknown: ".
+    pub fn as_str(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.name())
+    }
+
+    fn name(&self) -> &str {
+        match self {
             Self::Linux => "Linux",
             Self::Bsd => "BSD",
             Self::Windows => "Windows",
@@ -40,11 +370,53 @@ impl Display for Platform {
             Self::Ios => "iOS",
             Self::Android => "Android",
             Self::Nintendo => "Nintendo",
+            Self::NintendoSwitch => "Nintendo Switch",
             Self::Xbox => "XBox",
             Self::PlayStation => "PlayStation",
             Self::Fuchsia => "Fuchsia",
             Self::Redox => "Redox",
-            Self::Unknown(a) => a,
-        })
+            Self::Haiku => "Haiku",
+            Self::Wasi => "WASI",
+            Self::WebAssembly => "WebAssembly",
+            Self::Unknown(a) => a.as_str(),
+        }
+    }
+}
+
+/// An `Unknown` platform named "Unknown".
+impl Default for Platform {
+    fn default() -> Self {
+        Self::Unknown("Unknown".to_string())
+    }
+}
+
+impl AsRef<str> for Platform {
+    fn as_ref(&self) -> &str {
+        self.name()
+    }
+}
+
+impl From<Platform> for String {
+    fn from(value: Platform) -> Self {
+        match value {
+            Platform::Unknown(name) => name,
+            known => known.name().to_string(),
+        }
+    }
+}
+
+/// Case-insensitive comparison with the name returned by
+/// [`as_str()`](Platform::as_str).
+impl PartialEq<str> for Platform {
+    fn eq(&self, other: &str) -> bool {
+        self.name().eq_ignore_ascii_case(other)
+    }
+}
+
+/// Case-insensitive comparison with the name returned by
+/// [`as_str()`](Platform::as_str).
+impl PartialEq<&str> for Platform {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
     }
 }
@@ -0,0 +1,132 @@
+use crate::{fallible, Result};
+
+type Field = (&'static str, fn() -> Result<String>);
+
+/// Builder for querying multiple fields at once, using one fallback string for
+/// every field that fails.
+///
+/// Fields are queried in the order they were added when calling
+/// [`collect()`](Query::collect).
+///
+/// ```rust
+/// let fields = whoami::Query::new()
+///     .fallback("n/a")
+///     .username()
+///     .distro()
+///     .arch()
+///     .collect();
+///
+/// for (name, value) in fields {
+///     println!("{}: {}", name, value);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Query {
+    fallback: String,
+    fields: Vec<Field>,
+}
+
+impl Default for Query {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Query {
+    /// Create a new query with no fields, and a fallback of "Unknown".
+    pub fn new() -> Self {
+        Self {
+            fallback: "Unknown".to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Set the string used for fields that fail to be queried.
+    pub fn fallback(mut self, fallback: impl Into<String>) -> Self {
+        self.fallback = fallback.into();
+        self
+    }
+
+    /// Query [`fallible::username()`] as "username".
+    pub fn username(self) -> Self {
+        self.field("username", fallible::username)
+    }
+
+    /// Query [`fallible::realname()`] as "realname".
+    pub fn realname(self) -> Self {
+        self.field("realname", fallible::realname)
+    }
+
+    /// Query [`fallible::account()`] as "account".
+    pub fn account(self) -> Self {
+        self.field("account", fallible::account)
+    }
+
+    /// Query [`fallible::devicename()`] as "devicename".
+    pub fn devicename(self) -> Self {
+        self.field("devicename", fallible::devicename)
+    }
+
+    /// Query [`fallible::hostname()`] as "hostname".
+    pub fn hostname(self) -> Self {
+        self.field("hostname", fallible::hostname)
+    }
+
+    /// Query [`fallible::distro()`] as "distro".
+    pub fn distro(self) -> Self {
+        self.field("distro", fallible::distro)
+    }
+
+    /// Query [`fallible::desktop_env()`] as "desktop_env".
+    pub fn desktop_env(self) -> Self {
+        self.field("desktop_env", || Ok(fallible::desktop_env()?.to_string()))
+    }
+
+    /// Query [`fallible::platform()`] as "platform".
+    pub fn platform(self) -> Self {
+        self.field("platform", || Ok(fallible::platform()?.to_string()))
+    }
+
+    /// Query [`fallible::arch()`] as "arch".
+    pub fn arch(self) -> Self {
+        self.field("arch", || Ok(fallible::arch()?.to_string()))
+    }
+
+    /// Query [`endianness()`](crate::endianness) as "endianness".
+    pub fn endianness(self) -> Self {
+        self.field("endianness", || Ok(crate::endianness().to_string()))
+    }
+
+    /// Query [`langs()`](crate::langs) as "langs", separated by ", ".
+    pub fn langs(self) -> Self {
+        self.field("langs", || {
+            let langs = crate::langs()?
+                .map(|lang| lang.to_string())
+                .collect::<Vec<_>>();
+
+            Ok(langs.join(", "))
+        })
+    }
+
+    /// Query each field, returning `(name, value)` pairs in the order the
+    /// fields were added.
+    pub fn collect(self) -> Vec<(&'static str, String)> {
+        let fallback = self.fallback;
+
+        self.fields
+            .into_iter()
+            .map(|(name, query)| {
+                (name, query().unwrap_or_else(|_| fallback.clone()))
+            })
+            .collect()
+    }
+
+    fn field(
+        mut self,
+        name: &'static str,
+        query: fn() -> Result<String>,
+    ) -> Self {
+        self.fields.push((name, query));
+        self
+    }
+}
@@ -0,0 +1,26 @@
+use std::fmt::{self, Display, Formatter};
+
+/// The colors a terminal can display with ANSI escape sequences
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ColorSupport {
+    /// Colors aren't supported, or have been disabled with `NO_COLOR`
+    None,
+    /// The 16 standard ANSI colors
+    Ansi16,
+    /// The 256 color palette
+    Ansi256,
+    /// 24-bit RGB colors
+    TrueColor,
+}
+
+impl Display for ColorSupport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "None",
+            Self::Ansi16 => "16 colors",
+            Self::Ansi256 => "256 colors",
+            Self::TrueColor => "True color",
+        })
+    }
+}
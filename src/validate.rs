@@ -0,0 +1,139 @@
+/// Maximum length of a single label (dot-separated part) of a hostname.
+const MAX_LABEL_LEN: usize = 63;
+
+/// Maximum length of a hostname in bytes, including dots and not including a
+/// NUL terminator.
+///
+/// This is the limit [`hostname()`](crate::hostname) assumes, and the limit
+/// enforced by [`is_valid_hostname()`] and [`sanitize_hostname()`].
+pub const HOSTNAME_MAX_LEN: usize = 255;
+
+/// Maximum length of a username, not including a NUL terminator.
+///
+/// This is `UNLEN` on Windows (in UTF-16 code units).  On Linux, usernames are
+/// limited to 255 bytes, since `LOGIN_NAME_MAX` (256) includes the NUL
+/// terminator.
+pub const USERNAME_MAX_LEN: usize = 256;
+
+/// Returns true if `hostname` is a valid hostname.
+///
+/// A valid hostname is made of dot-separated labels of 1 to 63 characters,
+/// limited to a-z (case insensitive), 0-9, and dashes, where a label may not
+/// start or end with a dash.  The whole hostname is limited to 255 bytes.
+pub fn is_valid_hostname(hostname: &str) -> bool {
+    !hostname.is_empty()
+        && hostname.len() <= HOSTNAME_MAX_LEN
+        && hostname.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= MAX_LABEL_LEN
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
+/// Normalize `hostname` into a valid hostname (see [`is_valid_hostname()`]).
+///
+/// The hostname is lowercased, invalid characters are replaced with dashes,
+/// leading and trailing dashes are removed from each label, empty labels are
+/// removed, and labels and the whole hostname are truncated to their maximum
+/// lengths.  Returns "localhost" if nothing valid remains.
+///
+/// Example: "Jane's MacBook Pro" becomes "jane-s-macbook-pro"
+pub fn sanitize_hostname(hostname: &str) -> String {
+    let mut sanitized = String::with_capacity(hostname.len());
+
+    for label in hostname.split('.') {
+        let label = label
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect::<String>();
+        let mut label = label.trim_matches('-');
+
+        // Only ASCII remains, so byte indices are character boundaries
+        if label.len() > MAX_LABEL_LEN {
+            label = label[..MAX_LABEL_LEN].trim_end_matches('-');
+        }
+
+        if label.is_empty() {
+            continue;
+        }
+
+        let dot = if sanitized.is_empty() { 0 } else { 1 };
+
+        if sanitized.len() + dot + label.len() > HOSTNAME_MAX_LEN {
+            break;
+        }
+
+        if dot == 1 {
+            sanitized.push('.');
+        }
+
+        sanitized.push_str(label);
+    }
+
+    if sanitized.is_empty() {
+        return "localhost".to_string();
+    }
+
+    sanitized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_hostname() {
+        assert!(is_valid_hostname("localhost"));
+        assert!(is_valid_hostname("my-host.example.com"));
+        assert!(is_valid_hostname(&"a".repeat(MAX_LABEL_LEN)));
+        assert!(!is_valid_hostname(""));
+        assert!(!is_valid_hostname("-host"));
+        assert!(!is_valid_hostname("host-"));
+        assert!(!is_valid_hostname("my..host"));
+        assert!(!is_valid_hostname("Jane's MacBook Pro"));
+        assert!(!is_valid_hostname(&"a".repeat(MAX_LABEL_LEN + 1)));
+        assert!(is_valid_hostname(&["a"; 128].join(".")));
+        assert!(!is_valid_hostname(&["a"; 129].join(".")));
+    }
+
+    #[test]
+    fn sanitize_hostname_chars() {
+        assert_eq!(
+            sanitize_hostname("Jane's MacBook Pro"),
+            "jane-s-macbook-pro",
+        );
+        assert_eq!(sanitize_hostname("-host-.-.example-"), "host.example");
+        assert_eq!(sanitize_hostname("my..host."), "my.host");
+    }
+
+    #[test]
+    fn sanitize_hostname_truncates() {
+        let long_label = format!("{}-b", "a".repeat(MAX_LABEL_LEN - 1));
+
+        assert_eq!(sanitize_hostname(&long_label), "a".repeat(62));
+
+        let labels = vec!["a".repeat(MAX_LABEL_LEN); 5].join(".");
+        let sanitized = sanitize_hostname(&labels);
+
+        assert_eq!(sanitized.len(), HOSTNAME_MAX_LEN);
+        assert_eq!(sanitized, vec!["a".repeat(MAX_LABEL_LEN); 4].join("."));
+        assert!(is_valid_hostname(&sanitized));
+    }
+
+    #[test]
+    fn sanitize_hostname_fallback() {
+        assert_eq!(sanitize_hostname(""), "localhost");
+        assert_eq!(sanitize_hostname("---"), "localhost");
+        assert_eq!(sanitize_hostname("'.'"), "localhost");
+    }
+}